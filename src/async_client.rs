@@ -0,0 +1,182 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use chrono::NaiveDate;
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::boxscore::{from_box_score, BoxScore};
+use crate::career::{from_player_career, PlayerCareer};
+use crate::error::MlbError;
+use crate::gamelog::{from_game_log, GameLog};
+use crate::splits::{from_player_splits, PlayerSplits};
+use crate::roster::{from_roster, RosterEntry, RosterType};
+use crate::standings::{from_standings, DivisionStandings};
+use crate::live::{from_live_feed, LiveFeed};
+use crate::meta::{from_stats_meta, StatsMeta};
+use crate::schedule::{from_schedule, ScheduleDate, ScheduleQuery};
+use crate::leaders::{from_league_leaders, from_team_leaders, LeaderBoard};
+use crate::team::{from_team_stats, from_teams, Team, TeamStats};
+use crate::player::{from_player_details, from_player_stats, stat_group_param, Batter, Pitcher, PlayerStats, StatGroup};
+use crate::requests::{validate_base_url, Request};
+
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// Async counterpart to Transport. Returns a boxed future so implementations can be stored as trait objects
+pub trait AsyncTransport: Send + Sync {
+    fn get<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, Result<Value, MlbError>>;
+}
+
+pub struct AsyncHttpTransport {
+    client: reqwest::Client,
+}
+
+impl AsyncHttpTransport {
+    pub fn new(timeout: Option<Duration>, connect_timeout: Option<Duration>, user_agent: &str) -> Result<Self, MlbError> {
+        let mut builder = reqwest::Client::builder().user_agent(user_agent);
+
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(connect_timeout) = connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        Ok(AsyncHttpTransport { client: builder.build()? })
+    }
+}
+
+impl AsyncTransport for AsyncHttpTransport {
+    fn get<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, Result<Value, MlbError>> {
+        Box::pin(async move {
+            let resp = self.client.get(url.as_str()).send().await?;
+            if !resp.status().is_success() {
+                return Err(MlbError::Status {
+                    url: url.to_string(),
+                    status: resp.status().as_u16(),
+                });
+            }
+
+            Ok(serde_json::from_str(&resp.text().await?)?)
+        })
+    }
+}
+
+// Non-interactive async client over the same requests as MlbClient. All methods take &self, so independent
+// requests can be awaited concurrently (e.g. with tokio::join!)
+pub struct AsyncMlbClient {
+    base_url: String,
+    transport: Box<dyn AsyncTransport>,
+}
+
+impl AsyncMlbClient {
+    pub fn new(base_url: &str, transport: Box<dyn AsyncTransport>) -> Result<Self, MlbError> {
+        Ok(AsyncMlbClient { base_url: validate_base_url(base_url)?, transport })
+    }
+
+    async fn fetch(&self, request: Request) -> Result<Value, MlbError> {
+        let url = request.url(&self.base_url)?;
+        self.transport.get(&url).await
+    }
+
+    pub async fn get_teams(&self, season: &str) -> Result<Vec<Team>, MlbError> {
+        from_teams(self.fetch(Request::teams(season)).await?)
+    }
+
+    pub async fn get_players(&self, season: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::players(season)).await
+    }
+
+    pub async fn search_people(&self, name: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::search_people(name)).await
+    }
+
+    pub async fn get_player_details(&self, player_id: u64, player_type: &str, season: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::player_details(player_id, player_type, season)).await
+    }
+
+    pub async fn get_team_stats(&self, team_id: u64, season: &str) -> Result<TeamStats, MlbError> {
+        from_team_stats(self.fetch(Request::team_stats(team_id, season)).await?)
+    }
+
+    pub async fn get_stat_leaders(&self, leader_categories: &str, stat_type: &str, season: &str) -> Result<Vec<LeaderBoard>, MlbError> {
+        from_league_leaders(self.fetch(Request::stat_leaders(leader_categories, stat_type, season)).await?)
+    }
+
+    pub async fn get_team_stat_leaders(&self, team_id: u64, leader_categories: &str, season: &str) -> Result<Vec<LeaderBoard>, MlbError> {
+        from_team_leaders(self.fetch(Request::team_stat_leaders(team_id, leader_categories, season)).await?)
+    }
+
+    pub async fn get_stats_meta(&self) -> Result<StatsMeta, MlbError> {
+        let (leader_types, stat_groups, baseball_stats) = tokio::join!(
+            self.fetch(Request::leader_types()),
+            self.fetch(Request::stat_groups()),
+            self.fetch(Request::baseball_stats())
+        );
+        from_stats_meta(leader_types?, stat_groups?, baseball_stats?)
+    }
+
+    pub async fn get_box_score(&self, game_pk: u64) -> Result<BoxScore, MlbError> {
+        let (box_score, linescore) = tokio::join!(
+            self.fetch(Request::box_score(game_pk)),
+            self.fetch(Request::linescore(game_pk))
+        );
+        from_box_score(game_pk, box_score?, linescore?)
+    }
+
+    pub async fn get_live_feed(&self, game_pk: u64) -> Result<LiveFeed, MlbError> {
+        from_live_feed(self.fetch(Request::live_feed(game_pk)).await?)
+    }
+
+    pub async fn get_schedule(&self, query: &ScheduleQuery) -> Result<Vec<ScheduleDate>, MlbError> {
+        from_schedule(self.fetch(Request::schedule(query)).await?)
+    }
+
+    pub async fn get_standings(&self, league_ids: &[u64], season: &str, date: Option<NaiveDate>) -> Result<Vec<DivisionStandings>, MlbError> {
+        from_standings(self.fetch(Request::standings(league_ids, season, date)).await?)
+    }
+
+    pub async fn get_roster(&self, team_id: u64, roster_type: RosterType, season: &str, date: Option<NaiveDate>) -> Result<Vec<RosterEntry>, MlbError> {
+        from_roster(self.fetch(Request::roster(team_id, roster_type, season, date)).await?)
+    }
+
+    pub async fn get_batter(&self, player_id: u64, season: &str) -> Result<Batter, MlbError> {
+        match from_player_details(self.get_player_details(player_id, "hitting", season).await?)? {
+            Some(batter) => Ok(batter),
+            None => Err(self.get_player_career(player_id, &[StatGroup::Hitting], false).await?.no_stats(season)),
+        }
+    }
+
+    pub async fn get_pitcher(&self, player_id: u64, season: &str) -> Result<Pitcher, MlbError> {
+        match from_player_details(self.get_player_details(player_id, "pitching", season).await?)? {
+            Some(pitcher) => Ok(pitcher),
+            None => Err(self.get_player_career(player_id, &[StatGroup::Pitching], false).await?.no_stats(season)),
+        }
+    }
+
+    pub async fn get_player_stats(&self, player_id: u64, groups: &[StatGroup], season: &str) -> Result<PlayerStats, MlbError> {
+        from_player_stats(self.get_player_details(player_id, &stat_group_param(groups), season).await?)
+    }
+
+    pub async fn get_player_career(&self, player_id: u64, groups: &[StatGroup], advanced: bool) -> Result<PlayerCareer, MlbError> {
+        from_player_career(self.fetch(Request::player_career(player_id, &stat_group_param(groups), advanced)).await?)
+    }
+
+    pub async fn get_game_log(&self, player_id: u64, groups: &[StatGroup], season: &str) -> Result<GameLog, MlbError> {
+        from_game_log(self.fetch(Request::game_log(player_id, &stat_group_param(groups), season)).await?)
+    }
+
+    pub async fn get_player_splits(&self, player_id: u64, groups: &[StatGroup], sit_codes: &[String], season: &str) -> Result<PlayerSplits, MlbError> {
+        let request = Request::player_splits(player_id, &stat_group_param(groups), sit_codes, season);
+        from_player_splits(self.fetch(request).await?, sit_codes)
+    }
+
+    // Fetches hitting and pitching details at the same time
+    pub async fn get_batter_and_pitcher(&self, player_id: u64, season: &str) -> Result<(Batter, Pitcher), MlbError> {
+        let (batter, pitcher) = tokio::join!(
+            self.get_batter(player_id, season),
+            self.get_pitcher(player_id, season)
+        );
+        Ok((batter?, pitcher?))
+    }
+}
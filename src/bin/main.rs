@@ -1,31 +1,347 @@
-use std::io;
+use std::{io, process};
+use std::path::PathBuf;
+use chrono::Datelike;
+use clap::{Parser, Subcommand};
+
+use mlb::{MlbClientBuilder, MlbError, OutputFormat, RosterType, StatGroup, StatlineMode, DEFAULT_LEADER_LIMIT};
 
 /*
     MLB Stat Searcher
 
-    CLI app that can look up stats for players/teams in a season
+    CLI app that can look up stats for players/teams in a season. Runs an interactive menu when no
+    subcommand is given, and any value not passed as a flag is prompted for.
 */
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let choice = get_entry();
-    let mut mlb_client = mlb::create_client(io::stdin().lock());
+#[derive(Parser)]
+#[command(name = "mlb", version, about = "Look up stats for MLB players and teams in a season")]
+struct Cli {
+    /// Save every statsapi response to this directory
+    #[arg(long, global = true, value_name = "DIR", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Serve statsapi responses from a directory made with --record instead of the network
+    #[arg(long, global = true, value_name = "DIR")]
+    replay: Option<PathBuf>,
+
+    /// Always fetch fresh responses instead of using the on-disk cache
+    #[arg(long, global = true)]
+    no_cache: bool,
+
+    /// Directory for cached responses (defaults to the platform cache directory)
+    #[arg(long, global = true, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Season year to look up (prompted for if missing)
+    #[arg(long, global = true)]
+    season: Option<String>,
+
+    /// Output format: table, json, csv or markdown
+    #[arg(long, global = true, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Search for a player and print their statline
+    Player {
+        /// Name (or part of a name) to search for
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Stat groups to show, e.g. hitting,pitching (defaults to the player's position, both for two-way players)
+        #[arg(long, value_delimiter = ',')]
+        group: Vec<StatGroup>,
+
+        /// Also print a line for each team the player was on, for players traded mid-season
+        #[arg(long, conflicts_with = "career")]
+        by_team: bool,
+
+        /// Print every stat in the statline with its label, instead of the short summary
+        #[arg(long, conflicts_with_all = ["career", "by_team"])]
+        full: bool,
+
+        /// Print the full statline on one line
+        #[arg(long, requires = "full")]
+        compact: bool,
+
+        /// Print every season the player has played plus their career totals, instead of one season
+        #[arg(long)]
+        career: bool,
+
+        /// Add the advanced year by year stats to the career tables
+        #[arg(long, requires = "career")]
+        advanced: bool,
+    },
+    /// Print a player's game by game lines for the season
+    Gamelog {
+        /// Name (or part of a name) to search for
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Stat groups to show, e.g. hitting,pitching (defaults to the player's position, both for two-way players)
+        #[arg(long, value_delimiter = ',')]
+        group: Vec<StatGroup>,
+
+        /// Only show the last N games
+        #[arg(long, value_name = "N")]
+        last: Option<usize>,
 
-    match choice.as_str() {
-        "1" => mlb_client.get_player(),
-        "2" => mlb_client.get_team_stats(),
-        "3" => mlb_client.get_stat_leaders(),
-        "4" => mlb_client.get_team_stat_leaders(),
-        _ => panic!("This should not execute"),
+        /// Only show games on or after this day, YYYY-MM-DD
+        #[arg(long)]
+        start: Option<String>,
+
+        /// Only show games on or before this day, YYYY-MM-DD
+        #[arg(long)]
+        end: Option<String>,
+    },
+    /// Compare two or more players side by side, with a * after the better value in each row
+    Compare {
+        /// Names (or parts of names) to search for. Add a year to compare another season, e.g. "Buster Posey 2012"
+        #[arg(required = true, num_args = 2..)]
+        players: Vec<String>,
+    },
+    /// Print a player's stats split by situation, e.g. home/away or vs left/right-handed pitching
+    Splits {
+        /// Name (or part of a name) to search for
+        #[arg(long)]
+        name: Option<String>,
+
+        /// Stat groups to show, e.g. hitting,pitching (defaults to the player's position, both for two-way players)
+        #[arg(long, value_delimiter = ',')]
+        group: Vec<StatGroup>,
+
+        /// Situations to show: home-away, platoon, risp, day-night, months or situation codes like vl
+        /// (defaults to home-away, platoon, risp and day-night)
+        #[arg(long, value_delimiter = ',')]
+        sit: Vec<String>,
+    },
+    /// Print aggregate hitting and pitching stats for a team
+    TeamStats {
+        /// Team abbreviation, e.g. SF
+        #[arg(long)]
+        team: Option<String>,
+    },
+    /// Print league leaders in a stat category
+    Leaders {
+        /// Stat group, e.g. hitting, pitching or fielding
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Category abbreviation or statsapi name, e.g. HR, ERA or doubles
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Number of leaders to print
+        #[arg(long, default_value_t = DEFAULT_LEADER_LIMIT)]
+        limit: usize,
+    },
+    /// Print a team's leaders in a stat category
+    TeamLeaders {
+        /// Team abbreviation, e.g. SF
+        #[arg(long)]
+        team: Option<String>,
+
+        /// Stat group, e.g. hitting, pitching or fielding
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Category abbreviation or statsapi name, e.g. HR, ERA or doubles
+        #[arg(long)]
+        category: Option<String>,
+
+        /// Number of leaders to print
+        #[arg(long, default_value_t = DEFAULT_LEADER_LIMIT)]
+        limit: usize,
+    },
+    /// Print a team's roster, optionally followed by a player's statline
+    Roster {
+        /// Team abbreviation, e.g. SF
+        #[arg(long)]
+        team: Option<String>,
+
+        /// Roster type: active, 40Man, fullSeason or depthChart
+        #[arg(long = "type", default_value_t = RosterType::Active)]
+        roster_type: RosterType,
+
+        /// Show the roster as of this day, YYYY-MM-DD
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Jersey number or name of a player on the roster to print the statline for
+        #[arg(long)]
+        player: Option<String>,
+    },
+    /// Print a newspaper-style box score for a game
+    Boxscore {
+        /// Game id (gamePk), prompted for if missing
+        game_pk: Option<u64>,
+    },
+    /// Follow a game live, printing each play until it's over
+    Follow {
+        /// Game id (gamePk), or a team abbreviation to follow that team's game today
+        game: Option<String>,
+    },
+    /// List the games on a day with their status, score and probable pitchers
+    Scoreboard {
+        /// Day to show, YYYY-MM-DD (defaults to today)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Only show games for this team abbreviation, e.g. SF
+        #[arg(long)]
+        team: Option<String>,
+    },
+    /// Print the standings for each division, or the wild card race
+    Standings {
+        /// Only show one league, AL or NL
+        #[arg(long)]
+        league: Option<String>,
+
+        /// Show the standings as of this day, YYYY-MM-DD (defaults to the latest)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Rank the teams that aren't leading their division for the wild card spots
+        #[arg(long)]
+        wild_card: bool,
+    },
+    /// Manage the on-disk response cache
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheAction {
+    /// Delete every cached response
+    Clear,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
     }
+}
 
-    Ok(())
+fn run(cli: Cli) -> Result<(), MlbError> {
+    let cache_dir = cli.cache_dir.clone().or_else(mlb::default_cache_dir);
+
+    let interactive = cli.command.is_none();
+    let command = match cli.command {
+        Some(Command::Cache { action: CacheAction::Clear }) => {
+            return match cache_dir {
+                Some(dir) => {
+                    let removed = mlb::clear_cache(&dir)?;
+                    println!("Removed {} cached responses from {}", removed, dir.display());
+                    Ok(())
+                }
+                None => Err(MlbError::InvalidInput("Couldn't find a cache directory, pass --cache-dir".to_string())),
+            };
+        }
+        Some(command) => command,
+        None => get_entry()?,
+    };
+
+    // A game or a day already has its own season, so there's no point prompting for one
+    let season = match &command {
+        Command::Boxscore { .. } | Command::Follow { .. } => cli.season.or_else(|| Some(today().year().to_string())),
+        Command::Scoreboard { date, .. } => cli.season.or_else(|| {
+            let year = date.as_deref().and_then(|date| date.get(..4)).map(str::to_string);
+            Some(year.unwrap_or_else(|| today().year().to_string()))
+        }),
+        Command::Standings { date: Some(date), .. }
+        | Command::Roster { date: Some(date), .. }
+        | Command::Gamelog { start: Some(date), .. }
+        | Command::Gamelog { end: Some(date), .. } => cli.season.or_else(|| date.get(..4).map(str::to_string)),
+        // Players given with a year don't need a season, so only prompt when none of them have one
+        Command::Compare { players } => cli.season.or_else(|| {
+            players.iter().find_map(|player| {
+                let (_, year) = player.trim().rsplit_once(' ')?;
+                (year.len() == 4 && year.chars().all(|c| c.is_ascii_digit())).then(|| year.to_string())
+            })
+        }),
+        _ => cli.season,
+    };
+
+    let mut builder = MlbClientBuilder::new().season(season.as_deref()).format(cli.format);
+    if !cli.no_cache {
+        builder = builder.cache_dir(cache_dir.as_deref());
+    }
+    if let Some(dir) = &cli.record {
+        builder = builder.record(dir);
+    }
+    if let Some(dir) = &cli.replay {
+        builder = builder.replay(dir);
+    }
+
+    let mut mlb_client = builder.build(io::stdin().lock())?;
+
+    match command {
+        Command::Player { name, group, career: true, advanced, .. } => {
+            mlb_client.get_player_career_with(name.as_deref(), Some(&group), advanced)
+        }
+        Command::Player { name, group, full: true, compact, .. } => {
+            let mode = if compact { StatlineMode::Compact } else { StatlineMode::Standard };
+            mlb_client.get_player_full_stats_with(name.as_deref(), Some(&group), mode)
+        }
+        Command::Player { name, group, by_team: true, .. } => mlb_client.get_player_stats_by_team_with(name.as_deref(), Some(&group)),
+        Command::Player { name, group, .. } => mlb_client.get_player_stats_with(name.as_deref(), Some(&group)),
+        Command::Gamelog { name, group, last, start, end } => {
+            mlb_client.get_game_log_with(name.as_deref(), Some(&group), last, start.as_deref(), end.as_deref())
+        }
+        Command::Compare { players } => mlb_client.compare_players_with(&players),
+        Command::Splits { name, group, sit } => mlb_client.get_player_splits_with(name.as_deref(), Some(&group), Some(&sit)),
+        Command::TeamStats { team } => mlb_client.get_team_stats_with(team.as_deref()),
+        Command::Leaders { group, category, limit } => {
+            mlb_client.get_stat_leaders_with(group.as_deref(), category.as_deref(), limit)
+        }
+        Command::TeamLeaders { team, group, category, limit } => {
+            mlb_client.get_team_stat_leaders_with(team.as_deref(), group.as_deref(), category.as_deref(), limit)
+        }
+        // The menu prompts for everything, then lets the user pick a player off the roster
+        Command::Roster { team, date, player, .. } if interactive => {
+            mlb_client.get_roster_player_with(team.as_deref(), None, date.as_deref(), player.as_deref())
+        }
+        Command::Roster { team, roster_type, date, player: Some(player) } => {
+            mlb_client.get_roster_player_with(team.as_deref(), Some(roster_type), date.as_deref(), Some(&player))
+        }
+        Command::Roster { team, roster_type, date, player: None } => {
+            mlb_client.get_roster_with(team.as_deref(), Some(roster_type), date.as_deref())
+        }
+        Command::Boxscore { game_pk } => mlb_client.get_box_score_with(game_pk),
+        Command::Follow { game } => mlb_client.follow_with(game.as_deref()),
+        // The menu prompts for a date, the subcommand defaults to today
+        Command::Scoreboard { date, team } if interactive => mlb_client.get_scoreboard_with(date.as_deref(), team.as_deref()),
+        Command::Scoreboard { date, team } => {
+            let date = date.unwrap_or_else(|| today().to_string());
+            mlb_client.get_scoreboard_with(Some(&date), team.as_deref())
+        }
+        Command::Standings { league, date, wild_card } => {
+            mlb_client.get_standings_with(league.as_deref(), date.as_deref(), wild_card)
+        }
+        Command::Cache { .. } => unreachable!("cache commands are handled before building the client"),
+    }
+}
+
+fn today() -> chrono::NaiveDate {
+    chrono::Local::now().naive_local().date()
 }
 
-fn read_input(line: &mut String) {
-    io::stdin().read_line(line).expect("Failed to read line");
+fn read_input(line: &mut String) -> Result<(), MlbError> {
+    if io::stdin().read_line(line)? == 0 {
+        return Err(MlbError::InvalidInput("No option selected".to_string()));
+    }
+    Ok(())
 }
 
-fn get_entry() -> String{
+// Interactive menu, every option prompts for the rest of its input
+fn get_entry() -> Result<Command, MlbError> {
     println!("Entering MLB Client. Select action:");
 
     // Listing options
@@ -35,15 +351,54 @@ fn get_entry() -> String{
         println!("2) Get team stats");
         println!("3) Get stat leaders");
         println!("4) Get team stat leaders");
+        println!("5) Get a game box score");
+        println!("6) Get the scoreboard for a day");
+        println!("7) Follow a game live");
+        println!("8) Get the standings");
+        println!("9) Get a team roster");
+        println!("10) Get a player's career stats");
+        println!("11) Get a player's game log");
+        println!("12) Get a player's situational splits");
+        println!("13) Compare players side by side");
 
-        read_input(&mut choice);
+        read_input(&mut choice)?;
         match choice.trim() {
-            "1" | "2" | "3" | "4" => break,
-            _ => println!("Select an option between 1-4"),
+            "1" | "2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "10" | "11" | "12" | "13" => break,
+            _ => println!("Select an option between 1-13"),
         }
 
         choice.clear();
     }
 
-    choice.trim().to_string()
-}
\ No newline at end of file
+    Ok(match choice.trim() {
+        "1" => Command::Player { name: None, group: Vec::new(), by_team: false, full: false, compact: false, career: false, advanced: false },
+        "2" => Command::TeamStats { team: None },
+        "3" => Command::Leaders { group: None, category: None, limit: DEFAULT_LEADER_LIMIT },
+        "4" => Command::TeamLeaders { team: None, group: None, category: None, limit: DEFAULT_LEADER_LIMIT },
+        "5" => Command::Boxscore { game_pk: None },
+        "6" => Command::Scoreboard { date: None, team: None },
+        "7" => Command::Follow { game: None },
+        "8" => Command::Standings { league: None, date: None, wild_card: false },
+        "9" => Command::Roster { team: None, roster_type: RosterType::Active, date: None, player: None },
+        "10" => Command::Player { name: None, group: Vec::new(), by_team: false, full: false, compact: false, career: true, advanced: false },
+        "11" => Command::Gamelog { name: None, group: Vec::new(), last: None, start: None, end: None },
+        "12" => Command::Splits { name: None, group: Vec::new(), sit: Vec::new() },
+        _ => Command::Compare { players: Vec::new() },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_global_flags_after_subcommand() {
+        let cli = Cli::try_parse_from(["mlb", "cache", "clear", "--no-cache", "--cache-dir", "cache"]).unwrap();
+        assert!(cli.no_cache);
+        assert_eq!(Some(PathBuf::from("cache")), cli.cache_dir);
+        assert!(matches!(cli.command, Some(Command::Cache { action: CacheAction::Clear })));
+
+        let cli = Cli::try_parse_from(["mlb", "cache", "clear", "--replay", "fixtures"]).unwrap();
+        assert_eq!(Some(PathBuf::from("fixtures")), cli.replay);
+    }
+}
@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+use std::io::Write;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::player::Person;
+use crate::team::Team;
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BoxScoreBatting {
+    pub at_bats: u64,
+    pub runs: u64,
+    pub hits: u64,
+    pub rbi: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub left_on_base: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct BoxScorePitching {
+    pub innings_pitched: String,
+    pub hits: u64,
+    pub runs: u64,
+    pub earned_runs: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub home_runs: u64,
+    pub number_of_pitches: u64,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BattingLine {
+    pub id: u64,
+    pub name: String,
+    pub position: String,
+    pub substitute: bool,
+    #[serde(flatten)]
+    pub stats: BoxScoreBatting,
+}
+
+// `note` is the decision shown next to the name, e.g. "(W, 15-9)"
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PitchingLine {
+    pub id: u64,
+    pub name: String,
+    pub note: Option<String>,
+    #[serde(flatten)]
+    pub stats: BoxScorePitching,
+}
+
+// Some game notes (e.g. the date at the bottom of the box) are a label with no value
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct BoxNote {
+    pub label: String,
+    pub value: Option<String>,
+}
+
+// A titled group of notes, e.g. BATTING with 2B, HR and RBI entries
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InfoSection {
+    pub title: String,
+    #[serde(rename(deserialize = "fieldList"))]
+    pub fields: Vec<BoxNote>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamBoxScore {
+    pub team: Team,
+    pub batters: Vec<BattingLine>,
+    pub pitchers: Vec<PitchingLine>,
+    pub batting_totals: BoxScoreBatting,
+    pub pitching_totals: BoxScorePitching,
+    pub notes: Vec<BoxNote>,
+    pub info: Vec<InfoSection>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct LineScoreTotals {
+    pub runs: u64,
+    pub hits: u64,
+    pub errors: u64,
+    pub left_on_base: u64,
+}
+
+// Runs are None for a half inning that wasn't played, e.g. the bottom of the 9th when the home team leads
+#[derive(Serialize, Debug, Clone)]
+pub struct InningRuns {
+    pub num: u64,
+    pub away: Option<u64>,
+    pub home: Option<u64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct LineScore {
+    pub innings: Vec<InningRuns>,
+    pub away: LineScoreTotals,
+    pub home: LineScoreTotals,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BoxScore {
+    pub game_pk: u64,
+    pub linescore: LineScore,
+    pub away: TeamBoxScore,
+    pub home: TeamBoxScore,
+    pub notes: Vec<BoxNote>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawPitching {
+    note: Option<String>,
+    #[serde(flatten)]
+    stats: BoxScorePitching,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawPlayerStats {
+    batting: BoxScoreBatting,
+    pitching: RawPitching,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawPosition {
+    abbreviation: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawPlayer {
+    person: Person,
+    #[serde(default)]
+    position: RawPosition,
+    batting_order: Option<String>,
+    #[serde(default)]
+    stats: RawPlayerStats,
+}
+
+#[derive(Deserialize)]
+struct RawTeamStats {
+    batting: BoxScoreBatting,
+    pitching: BoxScorePitching,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTeamBoxScore {
+    team: Team,
+    team_stats: RawTeamStats,
+    players: HashMap<String, RawPlayer>,
+    #[serde(default)]
+    batters: Vec<u64>,
+    #[serde(default)]
+    pitchers: Vec<u64>,
+    #[serde(default)]
+    note: Vec<BoxNote>,
+    #[serde(default)]
+    info: Vec<InfoSection>,
+}
+
+#[derive(Deserialize)]
+struct RawTeams {
+    away: RawTeamBoxScore,
+    home: RawTeamBoxScore,
+}
+
+#[derive(Deserialize)]
+struct RawBoxScore {
+    teams: RawTeams,
+    #[serde(default)]
+    info: Vec<BoxNote>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawHalfInning {
+    runs: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct RawInning {
+    num: u64,
+    #[serde(default)]
+    away: RawHalfInning,
+    #[serde(default)]
+    home: RawHalfInning,
+}
+
+#[derive(Deserialize)]
+struct RawLineScoreTeams {
+    away: LineScoreTotals,
+    home: LineScoreTotals,
+}
+
+#[derive(Deserialize)]
+struct RawLineScore {
+    #[serde(default)]
+    innings: Vec<RawInning>,
+    teams: RawLineScoreTeams,
+}
+
+impl RawTeamBoxScore {
+    fn player(&self, id: u64) -> Result<&RawPlayer, MlbError> {
+        self.players
+            .get(&format!("ID{}", id))
+            .ok_or_else(|| MlbError::MissingField(format!("players.ID{}", id)))
+    }
+
+    fn into_team_box_score(self) -> Result<TeamBoxScore, MlbError> {
+        // `batters` lists everyone in order of appearance, only players with a batting order spot get a line.
+        // Spots are "100", "200", ... with substitutes at "101", "102", ...
+        let mut batters = Vec::new();
+        for &id in &self.batters {
+            let player = self.player(id)?;
+            let order = match player.batting_order.as_deref().and_then(|order| order.parse::<u64>().ok()) {
+                Some(order) => order,
+                None => continue,
+            };
+            batters.push((order, BattingLine {
+                id,
+                name: player.person.full_name.to_string(),
+                position: player.position.abbreviation.to_string(),
+                substitute: order % 100 != 0,
+                stats: player.stats.batting.clone(),
+            }));
+        }
+        batters.sort_by_key(|(order, _)| *order);
+
+        let pitchers = self
+            .pitchers
+            .iter()
+            .map(|&id| {
+                let player = self.player(id)?;
+                Ok(PitchingLine {
+                    id,
+                    name: player.person.full_name.to_string(),
+                    note: player.stats.pitching.note.clone(),
+                    stats: player.stats.pitching.stats.clone(),
+                })
+            })
+            .collect::<Result<Vec<PitchingLine>, MlbError>>()?;
+
+        Ok(TeamBoxScore {
+            team: self.team,
+            batters: batters.into_iter().map(|(_, line)| line).collect(),
+            pitchers,
+            batting_totals: self.team_stats.batting,
+            pitching_totals: self.team_stats.pitching,
+            notes: self.note,
+            info: self.info,
+        })
+    }
+}
+
+// Builds a box score from the boxscore and linescore responses for the same game
+pub(crate) fn from_box_score(game_pk: u64, box_score: Value, linescore: Value) -> Result<BoxScore, MlbError> {
+    let box_score: RawBoxScore = serde_json::from_value(box_score)?;
+    let linescore: RawLineScore = serde_json::from_value(linescore)?;
+
+    Ok(BoxScore {
+        game_pk,
+        linescore: LineScore {
+            innings: linescore
+                .innings
+                .into_iter()
+                .map(|inning| InningRuns { num: inning.num, away: inning.away.runs, home: inning.home.runs })
+                .collect(),
+            away: linescore.teams.away,
+            home: linescore.teams.home,
+        },
+        away: box_score.teams.away.into_team_box_score()?,
+        home: box_score.teams.home.into_team_box_score()?,
+        notes: box_score.info,
+    })
+}
+
+fn short_name(team: &Team) -> &str {
+    team.abbreviation.as_deref().unwrap_or(&team.name)
+}
+
+fn write_notes(writer: &mut dyn Write, notes: &[BoxNote]) -> std::io::Result<()> {
+    for note in notes {
+        match &note.value {
+            Some(value) => writeln!(writer, "{}: {}", note.label, value)?,
+            None => writeln!(writer, "{}", note.label)?,
+        }
+    }
+    Ok(())
+}
+
+impl BoxScore {
+    // Newspaper-style box: line score, then each team's batting and pitching lines with their notes
+    pub fn print_box_score(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let (away, home) = (&self.linescore.away, &self.linescore.home);
+        writeln!(writer, "{} {}, {} {}", self.away.team.name, away.runs, self.home.team.name, home.runs)?;
+
+        write!(writer, "\n{: <10}", "")?;
+        for inning in &self.linescore.innings {
+            write!(writer, "{: >3}", inning.num)?;
+        }
+        writeln!(writer, "  {: >3}{: >3}{: >3}", "R", "H", "E")?;
+        for (team, totals, is_home) in [(&self.away.team, away, false), (&self.home.team, home, true)] {
+            write!(writer, "{: <10}", short_name(team))?;
+            for inning in &self.linescore.innings {
+                let runs = if is_home { inning.home } else { inning.away };
+                match runs {
+                    Some(runs) => write!(writer, "{: >3}", runs)?,
+                    None => write!(writer, "{: >3}", "x")?,
+                }
+            }
+            writeln!(writer, "  {: >3}{: >3}{: >3}", totals.runs, totals.hits, totals.errors)?;
+        }
+
+        for team in [&self.away, &self.home] {
+            writeln!(writer, "\n{: <30}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}", team.team.name, "AB", "R", "H", "RBI", "BB", "SO", "LOB")?;
+            let totals = std::iter::once(("Totals".to_string(), &team.batting_totals));
+            let lines = team.batters.iter().map(|batter| {
+                let indent = if batter.substitute { "  " } else { "" };
+                (format!("{}{} {}", indent, batter.name, batter.position), &batter.stats)
+            });
+            for (name, stats) in lines.chain(totals) {
+                writeln!(
+                    writer,
+                    "{: <30}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}",
+                    name, stats.at_bats, stats.runs, stats.hits, stats.rbi, stats.base_on_balls, stats.strike_outs, stats.left_on_base
+                )?;
+            }
+
+            if !team.notes.is_empty() {
+                writeln!(writer)?;
+                for note in &team.notes {
+                    writeln!(writer, "{}-{}", note.label, note.value.as_deref().unwrap_or_default())?;
+                }
+            }
+            for section in &team.info {
+                writeln!(writer, "\n{}", section.title)?;
+                write_notes(writer, &section.fields)?;
+            }
+        }
+
+        for team in [&self.away, &self.home] {
+            writeln!(writer, "\n{: <30}{: >6}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}", team.team.name, "IP", "H", "R", "ER", "BB", "SO", "HR")?;
+            for pitcher in &team.pitchers {
+                let name = match &pitcher.note {
+                    Some(note) => format!("{} {}", pitcher.name, note),
+                    None => pitcher.name.to_string(),
+                };
+                let stats = &pitcher.stats;
+                writeln!(
+                    writer,
+                    "{: <30}{: >6}{: >4}{: >4}{: >4}{: >4}{: >4}{: >4}",
+                    name, stats.innings_pitched, stats.hits, stats.runs, stats.earned_runs, stats.base_on_balls, stats.strike_outs, stats.home_runs
+                )?;
+            }
+        }
+
+        if !self.notes.is_empty() {
+            writeln!(writer)?;
+            write_notes(writer, &self.notes)?;
+        }
+        Ok(())
+    }
+}
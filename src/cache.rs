@@ -0,0 +1,272 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use chrono::{Datelike, NaiveDate};
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::requests::META_ENDPOINTS;
+use crate::record::{fixture_path, read_fixture, request_key, write_fixture};
+use crate::transport::Transport;
+
+const CURRENT_SEASON_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const LIVE_GAME_TTL: Duration = Duration::from_secs(60);
+const META_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+#[derive(Debug, PartialEq)]
+enum CachePolicy {
+    Never,
+    Forever,
+    For(Duration),
+    // Forever once the cached response shows the game is over, refreshed every minute until then
+    UntilFinal,
+}
+
+// Pulls the newest season a request refers to, either from the `season` query param or from inside a
+// hydrate expression like `stats(group=[hitting],type=season,season=2022)`
+fn requested_season(url: &Url) -> Option<i32> {
+    let key = request_key(url);
+    key.match_indices("season=")
+        .filter_map(|(index, pattern)| {
+            let start = index + pattern.len();
+            key.get(start..start + 4)?.parse::<i32>().ok()
+        })
+        .max()
+}
+
+// Last day a schedule request covers, from `date` or the end of a `startDate`/`endDate` range
+fn requested_date(url: &Url) -> Option<NaiveDate> {
+    url.query_pairs()
+        .filter(|(key, _)| key == "date" || key == "endDate")
+        .filter_map(|(_, value)| NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok())
+        .max()
+}
+
+// Completed seasons can't change, anything else (current season or no season at all) is refreshed daily.
+// Game URLs have no date to go by, so games are cached by their state instead, and schedules can change by
+// the minute until the day is over (with a day of slack for time zones).
+// The statsapi's lists of stats and leader categories hardly ever change
+fn cache_policy(url: &Url) -> CachePolicy {
+    let path = url.path();
+    if META_ENDPOINTS.iter().any(|endpoint| path.ends_with(&format!("/{}", endpoint))) {
+        return CachePolicy::For(META_TTL);
+    }
+    if path.ends_with("/feed/live") {
+        return CachePolicy::Never;
+    }
+    if path.contains("/game/") {
+        return CachePolicy::UntilFinal;
+    }
+    if path.ends_with("/schedule") {
+        let today = chrono::Utc::now().naive_utc().date();
+        return match requested_date(url) {
+            Some(date) if today.pred_opt().is_some_and(|yesterday| date < yesterday) => CachePolicy::Forever,
+            _ => CachePolicy::For(LIVE_GAME_TTL),
+        };
+    }
+
+    let current_year = chrono::Utc::now().year();
+    match requested_season(url) {
+        Some(season) if season < current_year => CachePolicy::Forever,
+        _ => CachePolicy::For(CURRENT_SEASON_TTL),
+    }
+}
+
+// Box scores and line scores don't carry the game's status. The statsapi only adds the time of game ("T") to a
+// box score's info once the game is over, and a line score is over once the last inning has been decided:
+// three outs in the top with the home team ahead, or the bottom with the home team ahead (a walk-off) or three
+// outs with the away team ahead. A game that's called early stays on the live TTL
+fn is_final_game(response: &Value) -> bool {
+    if let Some(info) = response["info"].as_array() {
+        return info.iter().any(|entry| entry["label"] == "T");
+    }
+
+    let (inning, scheduled) = (response["currentInning"].as_u64(), response["scheduledInnings"].as_u64());
+    let (away, home) = (response["teams"]["away"]["runs"].as_u64(), response["teams"]["home"]["runs"].as_u64());
+    let three_outs = response["outs"].as_u64() == Some(3);
+    match (inning, scheduled, away, home, response["isTopInning"].as_bool()) {
+        (Some(inning), Some(scheduled), Some(away), Some(home), Some(is_top)) if inning >= scheduled => {
+            if is_top {
+                home > away && three_outs
+            } else {
+                home > away || (away > home && three_outs)
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_fresh(path: &Path, policy: &CachePolicy, response: &Value) -> bool {
+    let age_under = |ttl: Duration| {
+        fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age < ttl)
+    };
+
+    match policy {
+        CachePolicy::Never => false,
+        CachePolicy::Forever => true,
+        CachePolicy::For(ttl) => age_under(*ttl),
+        CachePolicy::UntilFinal => is_final_game(response) || age_under(LIVE_GAME_TTL),
+    }
+}
+
+// Platform cache directory, e.g. ~/.cache/mlb on Linux
+pub fn default_cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        return Some(PathBuf::from(dir).join("mlb"));
+    }
+    if let Some(dir) = std::env::var_os("LOCALAPPDATA") {
+        return Some(PathBuf::from(dir).join("mlb"));
+    }
+
+    std::env::var_os("HOME").map(|home| {
+        let home = PathBuf::from(home);
+        if cfg!(target_os = "macos") {
+            home.join("Library").join("Caches").join("mlb")
+        } else {
+            home.join(".cache").join("mlb")
+        }
+    })
+}
+
+// Removes cached responses from `dir`, returning how many were deleted
+pub fn clear_cache(dir: &Path) -> Result<usize, MlbError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            fs::remove_file(path)?;
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+// Serves responses from `dir` while they're fresh, otherwise goes through the wrapped transport and
+// caches the result
+pub struct CachingTransport<T> {
+    inner: T,
+    dir: PathBuf,
+}
+
+impl<T> CachingTransport<T> where T: Transport {
+    pub fn new(inner: T, dir: &Path) -> Self {
+        CachingTransport { inner, dir: dir.to_path_buf() }
+    }
+}
+
+impl<T> Transport for CachingTransport<T> where T: Transport {
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        let policy = cache_policy(url);
+        if policy == CachePolicy::Never {
+            return self.inner.get(url);
+        }
+
+        let path = fixture_path(&self.dir, url);
+        // A corrupt or mismatched cache entry is treated as a miss rather than an error
+        if let Ok(Some(response)) = read_fixture(&path, url) {
+            if is_fresh(&path, &policy, &response) {
+                return Ok(response);
+            }
+        }
+
+        let response = self.inner.get(url)?;
+        write_fixture(&path, url, &response)?;
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use chrono::Datelike;
+    use reqwest::Url;
+    use serde_json::json;
+
+    use super::{cache_policy, is_fresh, requested_season, CachePolicy, CURRENT_SEASON_TTL, LIVE_GAME_TTL, META_TTL};
+
+    #[test]
+    fn test_requested_season_from_hydrate() {
+        let url = Url::parse_with_params(
+            "https://statsapi.mlb.com/api/v1/people",
+            &[("personIds", "663698"), ("hydrate", "stats(group=[hitting],type=season,season=2022),currentTeam")],
+        ).unwrap();
+
+        assert_eq!(Some(2022), requested_season(&url));
+    }
+
+    #[test]
+    fn test_past_seasons_never_expire() {
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/sports/1/players?season=2019").unwrap();
+        assert_eq!(CachePolicy::Forever, cache_policy(&url));
+    }
+
+    #[test]
+    fn test_current_season_expires_daily() {
+        let current_year = chrono::Utc::now().year();
+        let url = Url::parse(&format!("https://statsapi.mlb.com/api/v1/sports/1/players?season={}", current_year)).unwrap();
+        assert_eq!(CachePolicy::For(CURRENT_SEASON_TTL), cache_policy(&url));
+
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/teams?sportId=1").unwrap();
+        assert_eq!(CachePolicy::For(CURRENT_SEASON_TTL), cache_policy(&url));
+    }
+
+    #[test]
+    fn test_schedules_expire_until_the_day_is_over() {
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/schedule?sportId=1&date=2022-09-30").unwrap();
+        assert_eq!(CachePolicy::Forever, cache_policy(&url));
+
+        let today = chrono::Utc::now().naive_utc().date();
+        let url = Url::parse(&format!("https://statsapi.mlb.com/api/v1/schedule?sportId=1&date={}", today)).unwrap();
+        assert_eq!(CachePolicy::For(LIVE_GAME_TTL), cache_policy(&url));
+    }
+
+    #[test]
+    fn test_games_cached_until_final() {
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/game/662021/boxscore").unwrap();
+        assert_eq!(CachePolicy::UntilFinal, cache_policy(&url));
+
+        // Never written, so only a finished game counts as fresh
+        let path = Path::new("no-such-dir/game.json");
+        let final_box_score = json!({"info": [{"label": "WP", "value": "Webb."}, {"label": "T", "value": "2:41."}]});
+        let live_box_score = json!({"info": [{"label": "WP", "value": "Webb."}]});
+        assert!(is_fresh(path, &CachePolicy::UntilFinal, &final_box_score));
+        assert!(!is_fresh(path, &CachePolicy::UntilFinal, &live_box_score));
+
+        let linescore = |inning: u64, is_top: bool, outs: u64, away: u64, home: u64| {
+            json!({
+                "currentInning": inning, "scheduledInnings": 9, "isTopInning": is_top, "outs": outs,
+                "teams": {"away": {"runs": away}, "home": {"runs": home}}
+            })
+        };
+        assert!(is_fresh(path, &CachePolicy::UntilFinal, &linescore(9, true, 3, 3, 5)));
+        assert!(is_fresh(path, &CachePolicy::UntilFinal, &linescore(10, false, 1, 3, 4)));
+        assert!(is_fresh(path, &CachePolicy::UntilFinal, &linescore(9, false, 3, 5, 3)));
+        assert!(!is_fresh(path, &CachePolicy::UntilFinal, &linescore(9, true, 2, 3, 5)));
+        assert!(!is_fresh(path, &CachePolicy::UntilFinal, &linescore(9, true, 3, 5, 3)));
+        assert!(!is_fresh(path, &CachePolicy::UntilFinal, &linescore(9, false, 3, 4, 4)));
+        assert!(!is_fresh(path, &CachePolicy::UntilFinal, &linescore(7, false, 3, 3, 5)));
+
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/game/662021/feed/live").unwrap();
+        assert_eq!(CachePolicy::Never, cache_policy(&url));
+    }
+
+    #[test]
+    fn test_meta_expires_weekly() {
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/leagueLeaderTypes").unwrap();
+        assert_eq!(CachePolicy::For(META_TTL), cache_policy(&url));
+
+        let url = Url::parse("https://statsapi.mlb.com/api/v1/baseballStats").unwrap();
+        assert_eq!(CachePolicy::For(META_TTL), cache_policy(&url));
+    }
+}
@@ -0,0 +1,188 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DefaultOnError, DisplayFromStr};
+
+use crate::error::{as_array, MlbError};
+use crate::player::{Batter, Person, Pitcher, StatGroup};
+use crate::team::Team;
+
+// Rates are None where the statsapi has a placeholder, like "-.--" walks per strikeout with no strikeouts
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdvancedBatter {
+    pub plate_appearances: u64,
+    pub total_bases: u64,
+    pub extra_base_hits: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub babip: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub iso: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub pitches_per_plate_appearance: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub walks_per_plate_appearance: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_per_plate_appearance: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub home_runs_per_plate_appearance: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub walks_per_strikeout: Option<f64>,
+}
+
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AdvancedPitcher {
+    pub batters_faced: u64,
+    pub quality_starts: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub babip: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub ops: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_per9: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub base_on_balls_per9: Option<f64>,
+    // Misspelled in the API
+    #[serde(rename = "strikesoutsToWalks")]
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_to_walks: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub pitches_per_inning: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strike_percentage: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub walks_per_plate_appearance: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_per_plate_appearance: Option<f64>,
+}
+
+// One season with one team. A player who changed teams mid-season also gets a line with no team for the
+// whole season, where `num_teams` says how many teams it covers
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SeasonLine<T> {
+    pub season: String,
+    pub team: Option<Team>,
+    pub num_teams: Option<u64>,
+    pub stat: T,
+}
+
+// `advanced` is only filled in when the advanced year by year stats are asked for
+#[derive(Serialize, Debug)]
+pub struct Career<T, A> {
+    pub seasons: Vec<SeasonLine<T>>,
+    pub advanced: Vec<SeasonLine<A>>,
+    pub total: Option<T>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlayerCareer {
+    pub person: Person,
+    pub hitting: Option<Career<Batter, AdvancedBatter>>,
+    pub pitching: Option<Career<Pitcher, AdvancedPitcher>>,
+}
+
+impl PlayerCareer {
+    // Every season the player has hitting or pitching stats in, oldest first
+    pub fn seasons(&self) -> Vec<String> {
+        let hitting = self.hitting.iter().flat_map(|career| career.seasons.iter().map(|line| &line.season));
+        let pitching = self.pitching.iter().flat_map(|career| career.seasons.iter().map(|line| &line.season));
+        let mut seasons: Vec<String> = hitting.chain(pitching).cloned().collect();
+        seasons.sort();
+        seasons.dedup();
+        seasons
+    }
+
+    // The error for a season the player has no stats in
+    pub(crate) fn no_stats(self, season: &str) -> MlbError {
+        MlbError::NoStats { seasons: self.seasons(), player: self.person.full_name, season: season.to_string() }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DisplayName {
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct StatsEntry {
+    #[serde(rename = "type")]
+    kind: DisplayName,
+    group: DisplayName,
+    #[serde(default)]
+    splits: Vec<Value>,
+}
+
+fn splits<'a>(entries: &'a [StatsEntry], kind: &str, group: StatGroup) -> Option<&'a [Value]> {
+    entries
+        .iter()
+        .find(|entry| entry.kind.display_name == kind && entry.group.display_name == group.as_str())
+        .map(|entry| entry.splits.as_slice())
+}
+
+fn season_lines<T>(splits: &[Value]) -> Result<Vec<SeasonLine<T>>, MlbError> where T: DeserializeOwned {
+    splits.iter().map(|split| Ok(SeasonLine::deserialize(split)?)).collect()
+}
+
+fn career<T, A>(entries: &[StatsEntry], group: StatGroup) -> Result<Option<Career<T, A>>, MlbError>
+where
+    T: DeserializeOwned,
+    A: DeserializeOwned,
+{
+    let seasons = match splits(entries, "yearByYear", group) {
+        Some(seasons) if !seasons.is_empty() => seasons,
+        _ => return Ok(None),
+    };
+
+    Ok(Some(Career {
+        seasons: season_lines(seasons)?,
+        advanced: splits(entries, "yearByYearAdvanced", group).map(season_lines).transpose()?.unwrap_or_default(),
+        total: splits(entries, "career", group)
+            .and_then(|splits| splits.first())
+            .map(|split| T::deserialize(&split["stat"]))
+            .transpose()?,
+    }))
+}
+
+// Reads the year by year (and career) stats for every group in a player details response
+pub(crate) fn from_player_career(details: Value) -> Result<PlayerCareer, MlbError> {
+    let person = as_array(&details, "people")?
+        .first()
+        .ok_or_else(|| MlbError::MissingField("people".to_string()))?;
+    let entries: Vec<StatsEntry> = match person.get("stats") {
+        Some(stats) => Vec::deserialize(stats)?,
+        None => Vec::new(),
+    };
+
+    Ok(PlayerCareer {
+        person: Person::deserialize(person)?,
+        hitting: career(&entries, StatGroup::Hitting)?,
+        pitching: career(&entries, StatGroup::Pitching)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::AdvancedBatter;
+
+    #[test]
+    fn test_advanced_placeholder_rates() {
+        // Webb's one 2020 plate appearance was a sacrifice
+        let advanced: AdvancedBatter = serde_json::from_value(json!({
+            "plateAppearances": 1,
+            "babip": ".---",
+            "iso": ".000",
+            "walksPerStrikeout": "-.--"
+        }))
+        .unwrap();
+        assert_eq!(None, advanced.babip);
+        assert_eq!(Some(0.0), advanced.iso);
+        assert_eq!(None, advanced.walks_per_strikeout);
+    }
+}
@@ -0,0 +1,254 @@
+use serde_json::Value;
+
+use crate::player::StatGroup;
+
+// Everything the client knows about one stat: the label it's shown with (Baseball-Reference style), where the
+// statsapi keeps it and how it's ranked. Adding a stat is adding one of these and listing it in the views below
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatCategory {
+    pub abbreviation: &'static str,
+    pub description: &'static str,
+    pub group: StatGroup,
+    // The statsapi's `leaderCategories` name, None if it has no leaderboard for the stat
+    pub leader_name: Option<&'static str>,
+    // The field in the statsapi's stat objects
+    pub field: &'static str,
+    // Decimals the stat is shown with, 0 for counting stats
+    pub precision: usize,
+    pub lower_is_better: bool,
+}
+
+impl StatCategory {
+    const fn count(group: StatGroup, abbreviation: &'static str, description: &'static str, field: &'static str) -> Self {
+        StatCategory { abbreviation, description, group, leader_name: None, field, precision: 0, lower_is_better: false }
+    }
+
+    const fn rate(group: StatGroup, abbreviation: &'static str, description: &'static str, field: &'static str, precision: usize) -> Self {
+        StatCategory { precision, ..StatCategory::count(group, abbreviation, description, field) }
+    }
+
+    const fn leader(self, leader_name: &'static str) -> Self {
+        StatCategory { leader_name: Some(leader_name), ..self }
+    }
+
+    const fn lower_is_better(self) -> Self {
+        StatCategory { lower_is_better: true, ..self }
+    }
+
+    // The stat's value in a serialized stat group, None if it's missing or a placeholder
+    pub fn value(&self, stats: &Value) -> Option<f64> {
+        match &stats[self.field] {
+            Value::String(value) => value.parse().ok(),
+            value => value.as_f64(),
+        }
+    }
+
+    // Shown the way a box score does: ".273" rather than "0.273", and the statsapi's placeholder for no value
+    pub fn format(&self, value: Option<f64>) -> String {
+        StatCategory::format_value(value, self.precision)
+    }
+
+    pub(crate) fn format_value(value: Option<f64>, precision: usize) -> String {
+        match (value, precision) {
+            (None, 3) => ".---".to_string(),
+            (None, 2) => "-.--".to_string(),
+            (None, _) => "-".to_string(),
+            (Some(value), 3) if (0.0..1.0).contains(&value) => format!("{:.3}", value).trim_start_matches('0').to_string(),
+            (Some(value), precision) => format!("{:.*}", precision, value),
+        }
+    }
+}
+
+pub mod hitting {
+    use super::StatCategory;
+    use crate::player::StatGroup::Hitting;
+
+    pub const GAMES: StatCategory = StatCategory::count(Hitting, "G", "Games played", "gamesPlayed");
+    pub const PLATE_APPEARANCES: StatCategory = StatCategory::count(Hitting, "PA", "Plate appearances", "plateAppearances");
+    pub const AT_BATS: StatCategory = StatCategory::count(Hitting, "AB", "At bats", "atBats");
+    pub const RUNS: StatCategory = StatCategory::count(Hitting, "R", "Runs", "runs");
+    pub const HITS: StatCategory = StatCategory::count(Hitting, "H", "Hits", "hits").leader("hits");
+    pub const DOUBLES: StatCategory = StatCategory::count(Hitting, "2B", "Doubles", "doubles");
+    pub const TRIPLES: StatCategory = StatCategory::count(Hitting, "3B", "Triples", "triples");
+    pub const HOME_RUNS: StatCategory = StatCategory::count(Hitting, "HR", "Home runs", "homeRuns").leader("homeRuns");
+    pub const RUNS_BATTED_IN: StatCategory = StatCategory::count(Hitting, "RBI", "Runs batted in", "rbi").leader("runsBattedIn");
+    pub const STOLEN_BASES: StatCategory = StatCategory::count(Hitting, "SB", "Stolen bases", "stolenBases").leader("stolenBases");
+    pub const CAUGHT_STEALING: StatCategory = StatCategory::count(Hitting, "CS", "Caught stealing", "caughtStealing").lower_is_better();
+    pub const WALKS: StatCategory = StatCategory::count(Hitting, "BB", "Walks", "baseOnBalls").leader("walks");
+    pub const INTENTIONAL_WALKS: StatCategory = StatCategory::count(Hitting, "IBB", "Intentional walks", "intentionalWalks");
+    pub const STRIKEOUTS: StatCategory = StatCategory::count(Hitting, "SO", "Strikeouts", "strikeOuts").leader("strikeouts").lower_is_better();
+    pub const HIT_BY_PITCH: StatCategory = StatCategory::count(Hitting, "HBP", "Hit by pitch", "hitByPitch").leader("hitByPitches");
+    pub const BATTING_AVERAGE: StatCategory = StatCategory::rate(Hitting, "BA", "Batting average", "avg", 3).leader("battingAverage");
+    pub const ON_BASE: StatCategory = StatCategory::rate(Hitting, "OBP", "On-base percentage", "obp", 3).leader("onBasePercentage");
+    pub const SLUGGING: StatCategory = StatCategory::rate(Hitting, "SLG", "Slugging percentage", "slg", 3).leader("sluggingPercentage");
+    pub const ON_BASE_PLUS_SLUGGING: StatCategory =
+        StatCategory::rate(Hitting, "OPS", "On-base plus slugging", "ops", 3).leader("onBasePlusSlugging");
+    pub const BABIP: StatCategory = StatCategory::rate(Hitting, "BAbip", "Batting average on balls in play", "babip", 3);
+    pub const TOTAL_BASES: StatCategory = StatCategory::count(Hitting, "TB", "Total bases", "totalBases");
+    pub const EXTRA_BASE_HITS: StatCategory = StatCategory::count(Hitting, "XBH", "Extra base hits", "extraBaseHits");
+    pub const ISOLATED_POWER: StatCategory = StatCategory::rate(Hitting, "ISO", "Isolated power", "iso", 3);
+    pub const PITCHES_PER_PLATE_APPEARANCE: StatCategory =
+        StatCategory::rate(Hitting, "P/PA", "Pitches per plate appearance", "pitchesPerPlateAppearance", 2);
+    pub const WALK_RATE: StatCategory = StatCategory::rate(Hitting, "BB%", "Walks per plate appearance", "walksPerPlateAppearance", 3);
+    pub const STRIKEOUT_RATE: StatCategory =
+        StatCategory::rate(Hitting, "SO%", "Strikeouts per plate appearance", "strikeoutsPerPlateAppearance", 3).lower_is_better();
+    pub const HOME_RUN_RATE: StatCategory =
+        StatCategory::rate(Hitting, "HR%", "Home runs per plate appearance", "homeRunsPerPlateAppearance", 3);
+    pub const WALKS_PER_STRIKEOUT: StatCategory = StatCategory::rate(Hitting, "BB/SO", "Walks per strikeout", "walksPerStrikeout", 2);
+
+    pub const ALL: &[StatCategory] = &[
+        GAMES, PLATE_APPEARANCES, AT_BATS, RUNS, HITS, DOUBLES, TRIPLES, HOME_RUNS, RUNS_BATTED_IN, STOLEN_BASES, CAUGHT_STEALING,
+        WALKS, INTENTIONAL_WALKS, STRIKEOUTS, HIT_BY_PITCH, BATTING_AVERAGE, ON_BASE, SLUGGING, ON_BASE_PLUS_SLUGGING, BABIP,
+        TOTAL_BASES, EXTRA_BASE_HITS, ISOLATED_POWER, PITCHES_PER_PLATE_APPEARANCE, WALK_RATE, STRIKEOUT_RATE, HOME_RUN_RATE,
+        WALKS_PER_STRIKEOUT,
+    ];
+    // A player's full statline, see `Player::stat_rows`
+    pub const STATLINE: &[StatCategory] = &[
+        GAMES, PLATE_APPEARANCES, AT_BATS, RUNS, HITS, DOUBLES, TRIPLES, HOME_RUNS, RUNS_BATTED_IN, STOLEN_BASES, CAUGHT_STEALING,
+        WALKS, STRIKEOUTS, HIT_BY_PITCH, BATTING_AVERAGE, ON_BASE, SLUGGING, ON_BASE_PLUS_SLUGGING, BABIP,
+    ];
+    // Columns of the career, splits and comparison tables
+    pub const PLAYER: &[StatCategory] = &[
+        GAMES, PLATE_APPEARANCES, AT_BATS, RUNS, HITS, DOUBLES, TRIPLES, HOME_RUNS, RUNS_BATTED_IN, STOLEN_BASES, CAUGHT_STEALING,
+        WALKS, STRIKEOUTS, HIT_BY_PITCH, BATTING_AVERAGE, ON_BASE, SLUGGING, ON_BASE_PLUS_SLUGGING,
+    ];
+    pub const TEAM: &[StatCategory] = &[
+        RUNS, HITS, DOUBLES, TRIPLES, HOME_RUNS, RUNS_BATTED_IN, STOLEN_BASES, CAUGHT_STEALING, WALKS, HIT_BY_PITCH,
+        INTENTIONAL_WALKS, STRIKEOUTS, BATTING_AVERAGE, ON_BASE, SLUGGING, ON_BASE_PLUS_SLUGGING,
+    ];
+    pub const ADVANCED: &[StatCategory] = &[
+        PLATE_APPEARANCES, TOTAL_BASES, EXTRA_BASE_HITS, BABIP, ISOLATED_POWER, PITCHES_PER_PLATE_APPEARANCE, WALK_RATE,
+        STRIKEOUT_RATE, HOME_RUN_RATE, WALKS_PER_STRIKEOUT,
+    ];
+}
+
+pub mod pitching {
+    use super::StatCategory;
+    use crate::player::StatGroup::Pitching;
+
+    pub const WINS: StatCategory = StatCategory::count(Pitching, "W", "Wins", "wins").leader("wins");
+    pub const LOSSES: StatCategory = StatCategory::count(Pitching, "L", "Losses", "losses").leader("losses").lower_is_better();
+    pub const WIN_PERCENTAGE: StatCategory = StatCategory::rate(Pitching, "W-L%", "Win-loss percentage", "winPercentage", 3);
+    pub const EARNED_RUN_AVERAGE: StatCategory =
+        StatCategory::rate(Pitching, "ERA", "Earned run average", "era", 2).leader("earnedRunAverage").lower_is_better();
+    pub const GAMES: StatCategory = StatCategory::count(Pitching, "G", "Games pitched", "gamesPitched");
+    pub const GAMES_STARTED: StatCategory = StatCategory::count(Pitching, "GS", "Games started", "gamesStarted");
+    pub const GAMES_FINISHED: StatCategory = StatCategory::count(Pitching, "GF", "Games finished", "gamesFinished");
+    pub const COMPLETE_GAMES: StatCategory = StatCategory::count(Pitching, "CG", "Complete games", "completeGames");
+    pub const SHUTOUTS: StatCategory = StatCategory::count(Pitching, "SHO", "Shutouts", "shutouts").leader("shutouts");
+    pub const HOLDS: StatCategory = StatCategory::count(Pitching, "HLD", "Holds", "holds").leader("holds");
+    pub const SAVES: StatCategory = StatCategory::count(Pitching, "SV", "Saves", "saves").leader("saves");
+    pub const INNINGS_PITCHED: StatCategory =
+        StatCategory::rate(Pitching, "IP", "Innings pitched", "inningsPitched", 1).leader("inningsPitched");
+    pub const HITS: StatCategory = StatCategory::count(Pitching, "H", "Hits allowed", "hits").lower_is_better();
+    pub const RUNS: StatCategory = StatCategory::count(Pitching, "R", "Runs allowed", "runs").lower_is_better();
+    pub const EARNED_RUNS: StatCategory = StatCategory::count(Pitching, "ER", "Earned runs allowed", "earnedRuns").lower_is_better();
+    pub const HOME_RUNS: StatCategory =
+        StatCategory::count(Pitching, "HR", "Home runs allowed", "homeRuns").leader("homeRuns").lower_is_better();
+    pub const WALKS: StatCategory = StatCategory::count(Pitching, "BB", "Walks", "baseOnBalls").leader("walks").lower_is_better();
+    pub const STRIKEOUTS: StatCategory = StatCategory::count(Pitching, "SO", "Strikeouts", "strikeOuts").leader("strikeouts");
+    pub const HIT_BATTERS: StatCategory =
+        StatCategory::count(Pitching, "HBP", "Hit batters", "hitByPitch").leader("hitBatsmen").lower_is_better();
+    pub const WHIP: StatCategory = StatCategory::rate(Pitching, "WHIP", "Walks and hits per inning pitched", "whip", 2)
+        .leader("walksAndHitsPerInningPitched")
+        .lower_is_better();
+    pub const HITS_PER_9: StatCategory = StatCategory::rate(Pitching, "H9", "Hits per 9 innings", "hitsPer9Inn", 2).lower_is_better();
+    pub const HOME_RUNS_PER_9: StatCategory =
+        StatCategory::rate(Pitching, "HR9", "Home runs per 9 innings", "homeRunsPer9", 2).lower_is_better();
+    pub const WALKS_PER_9: StatCategory =
+        StatCategory::rate(Pitching, "BB9", "Walks per 9 innings", "walksPer9Inn", 2).leader("walksPer9Inn").lower_is_better();
+    pub const STRIKEOUTS_PER_9: StatCategory =
+        StatCategory::rate(Pitching, "SO9", "Strikeouts per 9 innings", "strikeoutsPer9Inn", 2).leader("strikeoutsPer9Inn");
+    pub const STRIKEOUTS_PER_WALK: StatCategory = StatCategory::rate(Pitching, "SO/W", "Strikeouts per walk", "strikeoutWalkRatio", 2);
+    pub const OPPONENT_AVERAGE: StatCategory =
+        StatCategory::rate(Pitching, "BA", "Opponent batting average", "avg", 3).leader("battingAverage").lower_is_better();
+    pub const OPPONENT_ON_BASE: StatCategory =
+        StatCategory::rate(Pitching, "OBP", "Opponent on-base percentage", "obp", 3).leader("onBasePercentage").lower_is_better();
+    pub const OPPONENT_SLUGGING: StatCategory =
+        StatCategory::rate(Pitching, "SLG", "Opponent slugging percentage", "slg", 3).leader("sluggingPercentage").lower_is_better();
+    pub const OPPONENT_ON_BASE_PLUS_SLUGGING: StatCategory =
+        StatCategory::rate(Pitching, "OPS", "Opponent on-base plus slugging", "ops", 3).leader("onBasePlusSlugging").lower_is_better();
+    pub const BATTERS_FACED: StatCategory = StatCategory::count(Pitching, "BF", "Batters faced", "battersFaced");
+    pub const QUALITY_STARTS: StatCategory = StatCategory::count(Pitching, "QS", "Quality starts", "qualityStarts");
+    pub const BABIP: StatCategory =
+        StatCategory::rate(Pitching, "BAbip", "Opponent batting average on balls in play", "babip", 3).lower_is_better();
+    pub const PITCHES_PER_INNING: StatCategory =
+        StatCategory::rate(Pitching, "P/IP", "Pitches per inning", "pitchesPerInning", 2).lower_is_better();
+    pub const STRIKE_PERCENTAGE: StatCategory = StatCategory::rate(Pitching, "Str%", "Strike percentage", "strikePercentage", 3);
+    pub const WALK_RATE: StatCategory =
+        StatCategory::rate(Pitching, "BB%", "Walks per plate appearance", "walksPerPlateAppearance", 3).lower_is_better();
+    pub const STRIKEOUT_RATE: StatCategory =
+        StatCategory::rate(Pitching, "SO%", "Strikeouts per plate appearance", "strikeoutsPerPlateAppearance", 3);
+    // The yearByYearAdvanced lines name these differently than the season lines do
+    pub const ADVANCED_STRIKEOUTS_PER_9: StatCategory =
+        StatCategory::rate(Pitching, "SO9", "Strikeouts per 9 innings", "strikeoutsPer9", 2);
+    pub const ADVANCED_WALKS_PER_9: StatCategory =
+        StatCategory::rate(Pitching, "BB9", "Walks per 9 innings", "baseOnBallsPer9", 2).lower_is_better();
+    pub const ADVANCED_STRIKEOUTS_PER_WALK: StatCategory =
+        StatCategory::rate(Pitching, "SO/W", "Strikeouts per walk", "strikesoutsToWalks", 2);
+
+    pub const ALL: &[StatCategory] = &[
+        WINS, LOSSES, WIN_PERCENTAGE, EARNED_RUN_AVERAGE, GAMES, GAMES_STARTED, GAMES_FINISHED, COMPLETE_GAMES, SHUTOUTS, HOLDS,
+        SAVES, INNINGS_PITCHED, HITS, RUNS, EARNED_RUNS, HOME_RUNS, WALKS, STRIKEOUTS, HIT_BATTERS, WHIP, HITS_PER_9,
+        HOME_RUNS_PER_9, WALKS_PER_9, STRIKEOUTS_PER_9, STRIKEOUTS_PER_WALK, OPPONENT_AVERAGE, OPPONENT_ON_BASE, OPPONENT_SLUGGING,
+        OPPONENT_ON_BASE_PLUS_SLUGGING, BATTERS_FACED, QUALITY_STARTS, BABIP, PITCHES_PER_INNING, STRIKE_PERCENTAGE, WALK_RATE,
+        STRIKEOUT_RATE, ADVANCED_STRIKEOUTS_PER_9, ADVANCED_WALKS_PER_9, ADVANCED_STRIKEOUTS_PER_WALK,
+    ];
+    pub const STATLINE: &[StatCategory] = &[
+        WINS, LOSSES, WIN_PERCENTAGE, EARNED_RUN_AVERAGE, GAMES, GAMES_STARTED, GAMES_FINISHED, COMPLETE_GAMES, SHUTOUTS, HOLDS,
+        SAVES, INNINGS_PITCHED, HITS, RUNS, EARNED_RUNS, HOME_RUNS, WALKS, STRIKEOUTS, HIT_BATTERS, WHIP, HITS_PER_9,
+        HOME_RUNS_PER_9, WALKS_PER_9, STRIKEOUTS_PER_9, STRIKEOUTS_PER_WALK,
+    ];
+    pub const PLAYER: &[StatCategory] = &[
+        WINS, LOSSES, EARNED_RUN_AVERAGE, GAMES, GAMES_STARTED, GAMES_FINISHED, COMPLETE_GAMES, SHUTOUTS, SAVES, INNINGS_PITCHED,
+        HITS, RUNS, EARNED_RUNS, HOME_RUNS, WALKS, STRIKEOUTS, HIT_BATTERS, WHIP, HITS_PER_9, HOME_RUNS_PER_9, WALKS_PER_9,
+        STRIKEOUTS_PER_9, STRIKEOUTS_PER_WALK,
+    ];
+    // Wins, losses and starts don't mean much for a situation like vs left-handed batters, so pitching splits leave them out
+    pub const SPLITS: &[StatCategory] = &[
+        INNINGS_PITCHED, HITS, RUNS, EARNED_RUNS, HOME_RUNS, WALKS, STRIKEOUTS, HIT_BATTERS, EARNED_RUN_AVERAGE, WHIP, HITS_PER_9,
+        HOME_RUNS_PER_9, WALKS_PER_9, STRIKEOUTS_PER_9, STRIKEOUTS_PER_WALK,
+    ];
+    pub const TEAM: &[StatCategory] = &[
+        WINS, LOSSES, WIN_PERCENTAGE, EARNED_RUN_AVERAGE, COMPLETE_GAMES, SHUTOUTS, HOLDS, SAVES, INNINGS_PITCHED, HOME_RUNS, WALKS,
+        STRIKEOUTS, HIT_BATTERS, WHIP, HOME_RUNS_PER_9, WALKS_PER_9, STRIKEOUTS_PER_9, STRIKEOUTS_PER_WALK,
+    ];
+    pub const ADVANCED: &[StatCategory] = &[
+        BATTERS_FACED, QUALITY_STARTS, BABIP, OPPONENT_ON_BASE_PLUS_SLUGGING, ADVANCED_STRIKEOUTS_PER_9, ADVANCED_WALKS_PER_9,
+        ADVANCED_STRIKEOUTS_PER_WALK, PITCHES_PER_INNING, STRIKE_PERCENTAGE, WALK_RATE, STRIKEOUT_RATE,
+    ];
+}
+
+pub fn categories(group: StatGroup) -> &'static [StatCategory] {
+    match group {
+        StatGroup::Hitting => hitting::ALL,
+        StatGroup::Pitching => pitching::ALL,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_views_match_group() {
+        for group in [StatGroup::Hitting, StatGroup::Pitching] {
+            assert!(categories(group).iter().all(|category| category.group == group));
+        }
+        let views = [hitting::STATLINE, hitting::PLAYER, hitting::TEAM, hitting::ADVANCED];
+        assert!(views.iter().flat_map(|view| view.iter()).all(|category| hitting::ALL.contains(category)));
+        let views = [pitching::STATLINE, pitching::PLAYER, pitching::SPLITS, pitching::TEAM, pitching::ADVANCED];
+        assert!(views.iter().flat_map(|view| view.iter()).all(|category| pitching::ALL.contains(category)));
+    }
+
+    #[test]
+    fn test_format() {
+        let stats = json!({"avg": ".273", "era": 3.5, "homeRuns": 62, "whip": "-.--"});
+        assert_eq!(".273", hitting::BATTING_AVERAGE.format(hitting::BATTING_AVERAGE.value(&stats)));
+        assert_eq!("3.50", pitching::EARNED_RUN_AVERAGE.format(pitching::EARNED_RUN_AVERAGE.value(&stats)));
+        assert_eq!("62", hitting::HOME_RUNS.format(hitting::HOME_RUNS.value(&stats)));
+        assert_eq!("-.--", pitching::WHIP.format(pitching::WHIP.value(&stats)));
+    }
+}
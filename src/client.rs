@@ -1,241 +1,1301 @@
 use std::collections::HashMap;
-use std::io::BufRead;
-use serde_json::Value;
+use std::io::{BufRead, Stdout, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use serde::Serialize;
+use serde_json::{Map, Value};
 
-use crate::io::IOReader;
-use crate::player::{Batter, Pitcher, Player};
-use crate::requests::{get_players, get_stat_leaders, get_teams, get_team_stat_leaders, get_team_stats};
+use crate::cache::CachingTransport;
+use crate::career::SeasonLine;
+use crate::category::{hitting, pitching, StatCategory};
+use crate::error::{as_array, as_str, as_u64, MlbError};
+use crate::format::{write_output, OutputFormat, Table};
+use crate::leaders::LeaderEntry;
+use crate::io::{check_date, check_league, check_season, check_stat_type, IOReader};
+use crate::player::{Player, PlayerStats, StatGroup, StatRow, StatlineMode};
+use crate::record::{RecordingTransport, ReplayTransport};
+use crate::requests::{StatsApi, MLB_LOOKUP_API_ENDPOINT};
+use crate::live::{LiveEvent, LiveEventKind, LiveTracker};
+use crate::meta::{LeaderCategory, StatsMeta};
+use crate::roster::{find_roster_player, RosterEntry, RosterType};
+use crate::search::{exact_match, rank_players, search_players};
+use crate::splits::{check_sit_codes, SituationLine, DEFAULT_SIT_CODES};
+use crate::schedule::{ScheduleQuery, ScheduleTeam, ScheduledGame};
+use crate::standings::{league_name, wild_card_standings, TeamStanding, AMERICAN_LEAGUE_ID, NATIONAL_LEAGUE_ID};
+use crate::team::Team;
+use crate::transport::{HttpTransport, Transport};
 
-const HITTING_CATEGORIES: &'static [&'static str] = &["H", "HR", "RBI","SB","BB","HBP", "SO", "AVG", "OBP", "SLG", "OPS"];
-const PITCHING_CATEGORIES: &'static [&'static str] = &["W", "L", "ERA", "SHO", "HLD", "SV", "IP", "HR", "BB", "SO", "HBP", "WHIP", "BB9", "SO9", "AVG", "OBP", "SLG", "OPS"];
+// Picks the displayed columns out of a typed stat group, keyed by their labels
+// Missing values, like a rate the statsapi has a placeholder for, are shown as "-"
+fn stat_record<T>(stats: &T, columns: &[StatCategory]) -> Result<Map<String, Value>, MlbError> where T: Serialize {
+    let stat = serde_json::to_value(stats)?;
+    columns
+        .iter()
+        .map(|column| match stat.get(column.field) {
+            Some(Value::Null) => Ok((column.abbreviation.to_string(), Value::from("-"))),
+            Some(value) => Ok((column.abbreviation.to_string(), value.clone())),
+            None => Err(MlbError::MissingField(column.field.to_string())),
+        })
+        .collect()
+}
+
+fn split_table<T>(title: &str, lines: &[SituationLine<T>], columns: &[StatCategory]) -> Result<Table, MlbError> where T: Serialize {
+    let records = lines
+        .iter()
+        .map(|line| {
+            let mut record = Map::from_iter([("Split".to_string(), Value::from(line.situation.description.as_str()))]);
+            record.extend(stat_record(&line.stat, columns)?);
+            Ok(record)
+        })
+        .collect::<Result<Vec<Map<String, Value>>, MlbError>>()?;
+    Table::from_records(title, &records)
+}
+
+// One column per player and one row per stat, with a * after the best value in each row (all of them on a tie,
+// none if everyone's even)
+fn comparison_table(title: &str, players: &[(String, Map<String, Value>)], columns: &[StatCategory]) -> Result<Table, MlbError> {
+    let mut records = Vec::new();
+    let mut best = Vec::new();
+    for column in columns {
+        let label = column.abbreviation;
+        let mut record = Map::from_iter([("Stat".to_string(), Value::from(label))]);
+        let values: Vec<Option<f64>> = players.iter().map(|(_, stats)| stats[label].as_f64()).collect();
+        let known = values.iter().flatten();
+        let top = if column.lower_is_better { known.copied().reduce(f64::min) } else { known.copied().reduce(f64::max) };
+        let is_even = values.iter().all(|value| *value == values[0]);
+        best.push(values.iter().map(|value| !is_even && value.is_some() && *value == top).collect::<Vec<bool>>());
+        for (name, stats) in players {
+            record.insert(name.to_string(), stats[label].clone());
+        }
+        records.push(record);
+    }
+
+    let mut table = Table::from_records(title, &records)?;
+    for (row, best) in table.rows.iter_mut().zip(best) {
+        for (cell, is_best) in row.iter_mut().skip(1).zip(best) {
+            if is_best {
+                cell.push('*');
+            }
+        }
+    }
+    Ok(table)
+}
+
+// "Barry Bonds 2001" is Bonds' 2001 season, a name without a year is left to the client's season
+fn split_season(player: &str) -> Result<(&str, Option<&str>), MlbError> {
+    let player = player.trim();
+    match player.rsplit_once(' ') {
+        Some((name, year)) if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) => {
+            check_season(year)?;
+            Ok((name.trim(), Some(year)))
+        }
+        _ => Ok((player, None)),
+    }
+}
+
+// A player's stats in the comparison, along with the season they're from
+#[derive(Serialize)]
+struct ComparedPlayer {
+    season: String,
+    #[serde(flatten)]
+    stats: PlayerStats,
+}
+
+// Flattened leader entry for the tabular formats
+#[derive(Serialize)]
+struct LeaderRow<'a> {
+    rank: u64,
+    name: &'a str,
+    team: &'a str,
+    value: &'a str,
+}
+
+// One game per row of the scoreboard, with teams shown by abbreviation
+#[derive(Serialize)]
+struct ScoreboardRow {
+    away: String,
+    away_score: Option<u64>,
+    home: String,
+    home_score: Option<u64>,
+    status: String,
+    inning: String,
+    away_probable: String,
+    home_probable: String,
+}
+
+// One team per row of a division table, labeled like the standings on Baseball-Reference
+#[derive(Serialize)]
+struct StandingsRow {
+    #[serde(rename = "Tm")]
+    team: String,
+    #[serde(rename = "W")]
+    wins: u64,
+    #[serde(rename = "L")]
+    losses: u64,
+    #[serde(rename = "W-L%")]
+    winning_percentage: String,
+    #[serde(rename = "GB")]
+    games_back: String,
+    #[serde(rename = "WCGB")]
+    wild_card_games_back: String,
+    #[serde(rename = "Strk")]
+    streak: String,
+    #[serde(rename = "L10")]
+    last_ten: String,
+    #[serde(rename = "RDiff")]
+    run_differential: i64,
+    #[serde(rename = "Home")]
+    home: String,
+    #[serde(rename = "Away")]
+    away: String,
+}
+
+// How a player's season is shown: the short statline, plus each team's line, or every stat labeled
+#[derive(Clone, Copy)]
+enum PlayerView {
+    Statline,
+    ByTeam,
+    Full(StatlineMode),
+}
+
+#[derive(Serialize)]
+struct StatlineRow {
+    #[serde(rename = "Stat")]
+    label: &'static str,
+    #[serde(rename = "Value")]
+    value: String,
+    #[serde(rename = "Description")]
+    description: &'static str,
+}
+
+impl From<&StatRow> for StatlineRow {
+    fn from(row: &StatRow) -> Self {
+        StatlineRow { label: row.label, value: row.display_value(), description: row.description }
+    }
+}
+
+#[derive(Serialize)]
+struct RosterRow<'a> {
+    #[serde(rename = "#")]
+    jersey_number: &'a str,
+    #[serde(rename = "Name")]
+    name: &'a str,
+    #[serde(rename = "Pos")]
+    position: &'a str,
+    #[serde(rename = "B/T")]
+    bats_throws: String,
+    #[serde(rename = "Status")]
+    status: &'a str,
+}
+
+#[derive(Serialize)]
+struct GameBattingRow {
+    #[serde(rename = "Date")]
+    date: String,
+    #[serde(rename = "Opp")]
+    opponent: String,
+    #[serde(rename = "Rslt")]
+    result: &'static str,
+    #[serde(rename = "PA")]
+    plate_appearances: u64,
+    #[serde(rename = "AB")]
+    at_bats: u64,
+    #[serde(rename = "R")]
+    runs: u64,
+    #[serde(rename = "H")]
+    hits: u64,
+    #[serde(rename = "2B")]
+    doubles: u64,
+    #[serde(rename = "3B")]
+    triples: u64,
+    #[serde(rename = "HR")]
+    home_runs: u64,
+    #[serde(rename = "RBI")]
+    rbi: u64,
+    #[serde(rename = "BB")]
+    base_on_balls: u64,
+    #[serde(rename = "SO")]
+    strike_outs: u64,
+    #[serde(rename = "SB")]
+    stolen_bases: u64,
+    #[serde(rename = "LOB")]
+    left_on_base: u64,
+}
+
+#[derive(Serialize)]
+struct GamePitchingRow<'a> {
+    #[serde(rename = "Date")]
+    date: String,
+    #[serde(rename = "Opp")]
+    opponent: String,
+    #[serde(rename = "Rslt")]
+    result: &'static str,
+    #[serde(rename = "Dec")]
+    decision: String,
+    #[serde(rename = "IP")]
+    innings_pitched: &'a str,
+    #[serde(rename = "H")]
+    hits: u64,
+    #[serde(rename = "R")]
+    runs: u64,
+    #[serde(rename = "ER")]
+    earned_runs: u64,
+    #[serde(rename = "BB")]
+    base_on_balls: u64,
+    #[serde(rename = "SO")]
+    strike_outs: u64,
+    #[serde(rename = "HR")]
+    home_runs: u64,
+    #[serde(rename = "Pit")]
+    number_of_pitches: u64,
+}
+
+// Percentages are shown without the leading zero, e.g. .611
+fn format_pct(pct: f64) -> String {
+    let pct = format!("{:.3}", pct);
+    pct.strip_prefix('0').map(str::to_string).unwrap_or(pct)
+}
+
+pub const DEFAULT_LEADER_LIMIT: usize = 5;
+
+pub struct MlbClientBuilder {
+    base_url: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    user_agent: String,
+    transport: Option<Box<dyn Transport>>,
+    season: Option<String>,
+    format: OutputFormat,
+    cache_dir: Option<PathBuf>,
+    record_dir: Option<PathBuf>,
+    replay_dir: Option<PathBuf>,
+}
+
+impl Default for MlbClientBuilder {
+    fn default() -> Self {
+        MlbClientBuilder {
+            base_url: MLB_LOOKUP_API_ENDPOINT.to_string(),
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: None,
+            user_agent: format!("mlb/{}", env!("CARGO_PKG_VERSION")),
+            transport: None,
+            season: None,
+            format: OutputFormat::Table,
+            cache_dir: None,
+            record_dir: None,
+            replay_dir: None,
+        }
+    }
+}
+
+impl MlbClientBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn connect_timeout(mut self, connect_timeout: Option<Duration>) -> Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    // Replaces the default HTTP transport, in which case timeouts and user agent are up to the transport
+    pub fn transport<T>(mut self, transport: T) -> Self where T: Transport + 'static {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    // Uses this season instead of prompting for one when the client is built
+    pub fn season(mut self, season: Option<&str>) -> Self {
+        self.season = season.map(str::to_string);
+        self
+    }
+
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    // Caches responses in `dir`: completed seasons are kept forever, the current season is refreshed daily
+    pub fn cache_dir(mut self, dir: Option<&Path>) -> Self {
+        self.cache_dir = dir.map(Path::to_path_buf);
+        self
+    }
+
+    // Saves every response to `dir` so the same requests can later be served with `replay`
+    pub fn record(mut self, dir: &Path) -> Self {
+        self.record_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    // Serves responses from a directory made with `record` instead of going through the transport
+    pub fn replay(mut self, dir: &Path) -> Self {
+        self.replay_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    pub fn build_api(self) -> Result<StatsApi, MlbError> {
+        let transport: Box<dyn Transport> = match (self.replay_dir, self.record_dir) {
+            (Some(_), Some(_)) => return Err(MlbError::InvalidInput("Can't record and replay at the same time".to_string())),
+            (Some(replay_dir), None) => Box::new(ReplayTransport::new(&replay_dir)),
+            (None, record_dir) => {
+                let mut transport: Box<dyn Transport> = match self.transport {
+                    Some(transport) => transport,
+                    None => Box::new(HttpTransport::new(self.timeout, self.connect_timeout, &self.user_agent)?),
+                };
+                if let Some(cache_dir) = self.cache_dir {
+                    transport = Box::new(CachingTransport::new(transport, &cache_dir));
+                }
+
+                // Record outside the cache so cache hits end up in the recording too
+                match record_dir {
+                    Some(record_dir) => Box::new(RecordingTransport::new(transport, &record_dir)),
+                    None => transport,
+                }
+            }
+        };
+
+        StatsApi::new(&self.base_url, transport)
+    }
 
-pub struct MlbClient<'a, R> {
-    io_reader: IOReader<R>,
+    // The async client only supports the HTTP settings (base URL, timeouts, user agent)
+    #[cfg(feature = "async")]
+    pub fn build_async(self) -> Result<crate::async_client::AsyncMlbClient, MlbError> {
+        if self.transport.is_some() || self.cache_dir.is_some() || self.record_dir.is_some() || self.replay_dir.is_some() {
+            return Err(MlbError::InvalidInput(
+                "Custom transports, caching, recording and replay aren't supported by the async client".to_string(),
+            ));
+        }
+
+        let transport = crate::async_client::AsyncHttpTransport::new(self.timeout, self.connect_timeout, &self.user_agent)?;
+        crate::async_client::AsyncMlbClient::new(&self.base_url, Box::new(transport))
+    }
+
+    pub fn build<R>(self, reader: R) -> Result<MlbClient<R, Stdout>, MlbError> where R: BufRead {
+        self.build_with_output(reader, std::io::stdout())
+    }
+
+    // Like `build`, but prompts and stats are written to `writer` instead of stdout
+    pub fn build_with_output<R, W>(mut self, reader: R, writer: W) -> Result<MlbClient<R, W>, MlbError>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let season = self.season.take();
+        let format = self.format;
+        MlbClient::with_api(self.build_api()?, reader, writer, season, format)
+    }
+}
+
+pub struct MlbClient<R, W = Stdout> {
+    api: StatsApi,
+    io_reader: IOReader<R, W>,
+    format: OutputFormat,
     season: String,
     team_id_map: HashMap<u64, String>,
-    hitting_leader_categories: HashMap<&'a str, &'a str>,
-    pitching_leader_categories: HashMap<&'a str, &'a str>,
+    // Fetched the first time leaders are asked for
+    stats_meta: Option<StatsMeta>,
 }
 
-impl<R> MlbClient<'_, R> where R: BufRead {
-    pub fn new(reader: R) -> Self {
-        let mut io_reader = IOReader { reader };
-        let season = io_reader.get_season();
+impl<R> MlbClient<R, Stdout> where R: BufRead {
+    pub fn new(reader: R) -> Result<Self, MlbError> {
+        MlbClientBuilder::new().build(reader)
+    }
+}
 
-        let team_resp = get_teams(&season);
-        let team_id_map: HashMap<u64, String> = team_resp["teams"]
-            .as_array()
-            .unwrap()
-            .iter()
-            .map(|team| {
-                (
-                    team["id"].as_u64().unwrap(),
-                    String::from(team["abbreviation"].as_str().unwrap()),
-                )
+impl<R, W> MlbClient<R, W>
+where
+    R: BufRead,
+    W: Write,
+{
+    fn with_api(api: StatsApi, reader: R, writer: W, season: Option<String>, format: OutputFormat) -> Result<Self, MlbError> {
+        let mut io_reader = IOReader { reader, writer };
+        let season = match season {
+            Some(season) => {
+                check_season(&season)?;
+                season
+            }
+            None => io_reader.get_season()?,
+        };
+
+        let team_id_map: HashMap<u64, String> = api
+            .get_teams(&season)?
+            .into_iter()
+            .map(|team| match team.abbreviation {
+                Some(abbreviation) => Ok((team.id, abbreviation)),
+                None => Err(MlbError::MissingField("abbreviation".to_string())),
             })
-            .collect();
+            .collect::<Result<_, MlbError>>()?;
 
-        let hitting_leader_categories: HashMap<&str, &str> = HashMap::from([
-            ("H", "hits"),
-            ("HR", "homeRuns"),
-            ("RBI", "runsBattedIn"),
-            ("SB", "stolenBases"),
-            ("BB", "walks"),
-            ("HBP", "hitByPitches"),
-            ("SO", "strikeouts"),
-            ("AVG", "battingAverage"),
-            ("OBP", "onBasePercentage"),
-            ("SLG", "slugglingPercentage"),
-            ("OPS", "onBasePlusSlugging"),
-        ]);
-
-        let pitching_leader_categories: HashMap<&str, &str> = HashMap::from([
-            ("W", "wins"),
-            ("L", "losses"),
-            ("ERA", "earnedRunAverage"),
-            ("SHO", "shutouts"),
-            ("HLD", "holds"),
-            ("SV", "saves"),
-            ("IP", "inningsPitched"),
-            ("HR", "homeRuns"),
-            ("BB", "walks"),
-            ("SO", "strikeouts"),
-            ("HBP", "hitBatsmen"),
-            ("WHIP", "walksAndHitsPerInningPitched"),
-            ("BB9", "walksPer9Inn"),
-            ("SO9", "strikeoutsPer9Inn"),
-            ("AVG", "battingAverage"),
-            ("OBP", "onBasePercentage"),
-            ("SLG", "slugglingPercentage"),
-            ("OPS", "onBasePlusSlugging"),
-        ]);
-
-        MlbClient {
+        Ok(MlbClient {
+            api,
             io_reader,
+            format,
             season,
             team_id_map,
-            hitting_leader_categories,
-            pitching_leader_categories
+            stats_meta: None,
+        })
+    }
+
+    pub fn writer(&self) -> &W {
+        &self.io_reader.writer
+    }
+
+    pub fn into_writer(self) -> W {
+        self.io_reader.writer
+    }
+
+    // Resolves a team abbreviation (e.g. SF) to its id for the chosen season
+    fn find_team_id(&self, abbreviation: &str) -> Result<u64, MlbError> {
+        self.team_id_map
+            .iter()
+            .find(|(_, team)| team.eq_ignore_ascii_case(abbreviation))
+            .map(|(&id, _)| id)
+            .ok_or_else(|| MlbError::InvalidInput(format!("No team with abbreviation {} in {}", abbreviation, self.season)))
+    }
+
+    fn get_team_id(&mut self, team: Option<&str>) -> Result<u64, MlbError> {
+        match team {
+            Some(team) => self.find_team_id(team),
+            None => self.io_reader.get_team_id(&self.team_id_map),
+        }
+    }
+
+    fn stats_meta(&mut self) -> Result<&StatsMeta, MlbError> {
+        let stats_meta = match self.stats_meta.take() {
+            Some(stats_meta) => stats_meta,
+            None => self.api.get_stats_meta()?,
+        };
+        Ok(self.stats_meta.insert(stats_meta))
+    }
+
+    fn get_stat_type(&mut self, stat_type: Option<&str>) -> Result<String, MlbError> {
+        let groups = self.stats_meta()?.leader_groups();
+        match stat_type {
+            Some(stat_type) => check_stat_type(stat_type, &groups),
+            None => self.io_reader.get_stat_type(&groups),
+        }
+    }
+
+    // Returns the leader category name the API expects, prompting for one if no abbreviation (e.g. HR) or name
+    // (e.g. doubles) is given
+    fn get_leader_category(&mut self, stat_type: &str, category: Option<&str>) -> Result<String, MlbError> {
+        let stats_meta = self.stats_meta()?;
+        let category = match category {
+            Some(category) => stats_meta.find_leader_category(stat_type, category).ok_or_else(|| {
+                let leader_categories = stats_meta.leader_categories(stat_type);
+                let labels: Vec<&str> = leader_categories.iter().map(LeaderCategory::label).collect();
+                MlbError::InvalidInput(format!("Unknown {} category {}, expected one of {}", stat_type, category, labels.join(", ")))
+            })?,
+            None => {
+                let leader_categories = stats_meta.leader_categories(stat_type);
+                self.io_reader.get_leader_category(&leader_categories)?
+            }
+        };
+        Ok(category.name)
+    }
+
+    pub fn get_player(&mut self) -> Result<(), MlbError> {
+        self.get_player_with(None)
+    }
+
+    pub fn get_player_with(&mut self, name: Option<&str>) -> Result<(), MlbError> {
+        self.get_player_stats_with(name, None)
+    }
+
+    // Like `get_player_with`, but shows the given stat groups instead of the ones for the player's position
+    pub fn get_player_stats_with(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>) -> Result<(), MlbError> {
+        self.show_player_stats(name, groups, PlayerView::Statline)
+    }
+
+    // Like `get_player_stats_with`, and adds a line for each team a player traded mid-season was on
+    pub fn get_player_stats_by_team_with(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>) -> Result<(), MlbError> {
+        self.show_player_stats(name, groups, PlayerView::ByTeam)
+    }
+
+    // Every stat in the player's statlines with its label, instead of the short summary
+    pub fn get_player_full_stats_with(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>, mode: StatlineMode) -> Result<(), MlbError> {
+        self.show_player_stats(name, groups, PlayerView::Full(mode))
+    }
+
+    fn show_player_stats(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>, view: PlayerView) -> Result<(), MlbError> {
+        let (player_id, position) = self.find_player(name)?;
+        let groups = match groups {
+            Some(groups) if !groups.is_empty() => groups.to_vec(),
+            _ => StatGroup::defaults_for_position(&position),
+        };
+
+        let stats = PlayerStats::new(&self.api, player_id, &groups, &self.season)?;
+        self.check_player_stats(&stats, &self.season)?;
+        self.write_player_stats(&stats, &groups, view)
+    }
+
+    fn find_player(&mut self, name: Option<&str>) -> Result<(u64, String), MlbError> {
+        let season = self.season.clone();
+        self.find_player_in(name, &season)
+    }
+
+    // Searches the season's players by name, returning the id and position abbreviation of the one picked
+    fn find_player_in(&mut self, name: Option<&str>, season: &str) -> Result<(u64, String), MlbError> {
+        let name_query = match name {
+            Some(name) => name.to_string(),
+            None => self.io_reader.get_name_query()?,
+        };
+        let resp = self.api.get_players(season)?;
+        let mut matches = search_players(&name_query, as_array(&resp, "people")?);
+
+        // Falls back to every player the statsapi knows, for ones who aren't on the season's list (e.g. retired players)
+        let searched;
+        if matches.is_empty() {
+            searched = self.api.search_people(&name_query)?;
+            matches = rank_players(&name_query, as_array(&searched, "people")?);
+        }
+
+        let player_value: &Value = match (exact_match(&name_query, &matches), matches.as_slice()) {
+            (Some(player), _) => player,
+            (None, []) => return Err(MlbError::InvalidInput(format!("No players found matching \"{}\"", name_query))),
+            (None, [player]) => player,
+            (None, _) => self.io_reader.get_filtered_players(&self.team_id_map, &matches)?,
+        };
+
+        Ok((as_u64(player_value, "id")?, as_str(&player_value["primaryPosition"], "abbreviation")?.to_string()))
+    }
+
+    // A player with no stats at all in the season is an error listing the seasons they do have, while one with
+    // stats in some other group (e.g. a pitcher asked for hitting) is left to the output to mention
+    fn check_player_stats(&self, stats: &PlayerStats, season: &str) -> Result<(), MlbError> {
+        if stats.hitting.is_some() || stats.pitching.is_some() {
+            return Ok(());
+        }
+        let career = self.api.get_player_career(stats.person.id, &[StatGroup::Hitting, StatGroup::Pitching], false)?;
+        if career.seasons().iter().any(|other| other == season) {
+            return Ok(());
+        }
+        Err(career.no_stats(season))
+    }
+
+    // Lines up two or more players' stats side by side. Players can be from different seasons (e.g. "Barry Bonds 2001"),
+    // and are only compared in the stat groups they all have, so a two-way player next to a hitter compares hitting
+    pub fn compare_players_with(&mut self, players: &[String]) -> Result<(), MlbError> {
+        let players = match players {
+            [] => self.io_reader.get_compare_players()?,
+            players => players.to_vec(),
+        };
+        if players.len() < 2 {
+            return Err(MlbError::InvalidInput("Pick at least two players to compare".to_string()));
+        }
+
+        let mut found = Vec::new();
+        for player in &players {
+            let (name, season) = split_season(player)?;
+            let season = season.unwrap_or(&self.season).to_string();
+            let (player_id, position) = self.find_player_in(Some(name), &season)?;
+            found.push((player_id, StatGroup::defaults_for_position(&position), season));
+        }
+
+        let groups: Vec<StatGroup> = [StatGroup::Hitting, StatGroup::Pitching]
+            .into_iter()
+            .filter(|group| found.iter().all(|(_, groups, _)| groups.contains(group)))
+            .collect();
+        if groups.is_empty() {
+            return Err(MlbError::InvalidInput(
+                "Can't compare hitters with pitchers, the players don't share a stat group".to_string(),
+            ));
+        }
+
+        let mut compared = Vec::new();
+        for (player_id, _, season) in found {
+            let stats = PlayerStats::new(&self.api, player_id, &groups, &season)?;
+            self.check_player_stats(&stats, &season)?;
+            compared.push(ComparedPlayer { season, stats });
         }
+
+        let mut tables = Vec::new();
+        for group in &groups {
+            let columns = match group {
+                StatGroup::Hitting => hitting::PLAYER,
+                StatGroup::Pitching => pitching::PLAYER,
+            };
+            let mut lines: Vec<(String, Map<String, Value>)> = Vec::new();
+            for player in &compared {
+                let name = format!("{} ({})", player.stats.person.full_name, player.season);
+                let missing = || MlbError::InvalidInput(format!("No {} stats for {}", group, name));
+                let stats = match group {
+                    StatGroup::Hitting => stat_record(player.stats.hitting.as_ref().ok_or_else(missing)?, columns)?,
+                    StatGroup::Pitching => stat_record(player.stats.pitching.as_ref().ok_or_else(missing)?, columns)?,
+                };
+                if lines.iter().any(|(other, _)| *other == name) {
+                    return Err(MlbError::InvalidInput(format!("{} is in the comparison more than once", name)));
+                }
+                lines.push((name, stats));
+            }
+            let title = match group {
+                StatGroup::Hitting => "Hitting Comparison",
+                StatGroup::Pitching => "Pitching Comparison",
+            };
+            tables.push(comparison_table(title, &lines, columns)?);
+        }
+
+        write_output(&mut self.io_reader.writer, self.format, &compared, &tables)
     }
 
-    pub fn get_player(&mut self) {
-        let name_query = self.io_reader.get_name_query();
-        let resp = get_players(&self.season);
-        let players = resp["people"].as_array().unwrap();
+    // Baseball-Reference style career tables, one row per season and team plus the career totals
+    pub fn get_player_career_with(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>, advanced: bool) -> Result<(), MlbError> {
+        let (player_id, position) = self.find_player(name)?;
+        let groups = match groups {
+            Some(groups) if !groups.is_empty() => groups.to_vec(),
+            _ => StatGroup::defaults_for_position(&position),
+        };
+        let career = self.api.get_player_career(player_id, &groups, advanced)?;
+
+        let name = &career.person.full_name;
+        let mut tables = Vec::new();
+        if let Some(hitting) = &career.hitting {
+            tables.push(self.career_table(&format!("{} Batting", name), &hitting.seasons, hitting.total.as_ref(), hitting::PLAYER)?);
+            if !hitting.advanced.is_empty() {
+                tables.push(self.career_table(&format!("{} Advanced Batting", name), &hitting.advanced, None, hitting::ADVANCED)?);
+            }
+        }
+        if let Some(pitching) = &career.pitching {
+            tables.push(self.career_table(&format!("{} Pitching", name), &pitching.seasons, pitching.total.as_ref(), pitching::PLAYER)?);
+            if !pitching.advanced.is_empty() {
+                tables.push(self.career_table(&format!("{} Advanced Pitching", name), &pitching.advanced, None, pitching::ADVANCED)?);
+            }
+        }
+
+        let writer = &mut self.io_reader.writer;
+        if tables.is_empty() && self.format == OutputFormat::Table {
+            let groups = groups.iter().map(StatGroup::as_str).collect::<Vec<&str>>().join(" or ");
+            writeln!(writer, "No {} stats for {}", groups, name)?;
+            return Ok(());
+        }
+        write_output(writer, self.format, &career, &tables)
+    }
+
+    pub fn get_game_log(&mut self) -> Result<(), MlbError> {
+        self.get_game_log_with(None, None, None, None, None)
+    }
+
+    // A player's games in the season, optionally only the last `last` games and/or the games between `start` and `end`
+    pub fn get_game_log_with(
+        &mut self,
+        name: Option<&str>,
+        groups: Option<&[StatGroup]>,
+        last: Option<usize>,
+        start: Option<&str>,
+        end: Option<&str>,
+    ) -> Result<(), MlbError> {
+        let start = start.map(check_date).transpose()?;
+        let end = end.map(check_date).transpose()?;
+        let (player_id, position) = self.find_player(name)?;
+        let groups = match groups {
+            Some(groups) if !groups.is_empty() => groups.to_vec(),
+            _ => StatGroup::defaults_for_position(&position),
+        };
+
+        let mut game_log = self.api.get_game_log(player_id, &groups, &self.season)?.between(start, end);
+        if let Some(last) = last {
+            game_log = game_log.last(last);
+        }
 
-        let filtered_players: Vec<&Value> = players
+        // Away games are shown like "@LAD"
+        let opponent = |team: &Team, is_home: bool| {
+            let name = self.team_id_map.get(&team.id).cloned().unwrap_or_else(|| team.name.to_string());
+            if is_home { name } else { format!("@{}", name) }
+        };
+        let batting: Vec<GameBattingRow> = game_log
+            .hitting
             .iter()
-            .filter(|player| {
-                player["fullName"]
-                    .as_str()
-                    .unwrap()
-                    .to_lowercase()
-                    .contains(&name_query.trim().to_lowercase())
+            .map(|game| GameBattingRow {
+                date: game.date.to_string(),
+                opponent: opponent(&game.opponent, game.is_home),
+                result: game.result(),
+                plate_appearances: game.stat.plate_appearances,
+                at_bats: game.stat.at_bats,
+                runs: game.stat.runs,
+                hits: game.stat.hits,
+                doubles: game.stat.doubles,
+                triples: game.stat.triples,
+                home_runs: game.stat.home_runs,
+                rbi: game.stat.rbi,
+                base_on_balls: game.stat.base_on_balls,
+                strike_outs: game.stat.strike_outs,
+                stolen_bases: game.stat.stolen_bases,
+                left_on_base: game.stat.left_on_base,
             })
             .collect();
+        let pitching: Vec<GamePitchingRow> = game_log
+            .pitching
+            .iter()
+            .map(|game| GamePitchingRow {
+                date: game.date.to_string(),
+                opponent: opponent(&game.opponent, game.is_home),
+                result: game.result(),
+                decision: game.stat.decision(),
+                innings_pitched: &game.stat.innings_pitched,
+                hits: game.stat.hits,
+                runs: game.stat.runs,
+                earned_runs: game.stat.earned_runs,
+                base_on_balls: game.stat.base_on_balls,
+                strike_outs: game.stat.strike_outs,
+                home_runs: game.stat.home_runs,
+                number_of_pitches: game.stat.number_of_pitches,
+            })
+            .collect();
+
+        let name = &game_log.person.full_name;
+        let mut tables = Vec::new();
+        if !batting.is_empty() {
+            tables.push(Table::from_records(&format!("{} Batting Game Log", name), &batting)?);
+        }
+        if !pitching.is_empty() {
+            tables.push(Table::from_records(&format!("{} Pitching Game Log", name), &pitching)?);
+        }
+
+        let writer = &mut self.io_reader.writer;
+        if tables.is_empty() && self.format == OutputFormat::Table {
+            writeln!(writer, "No games for {} in {}", name, self.season)?;
+            return Ok(());
+        }
+        write_output(writer, self.format, &game_log, &tables)
+    }
 
-        let player_value: Option<&Value> = match filtered_players.len() {
-            0 => None,
-            1 => Some(filtered_players[0]),
-            _ => self.io_reader.get_filtered_players(&self.team_id_map, &filtered_players)
+    // One row per situation (e.g. vs Left, vs Right) with the same columns as the career tables
+    pub fn get_player_splits_with(&mut self, name: Option<&str>, groups: Option<&[StatGroup]>, sit_codes: Option<&[String]>) -> Result<(), MlbError> {
+        let sit_codes = match sit_codes {
+            Some(sit_codes) if !sit_codes.is_empty() => check_sit_codes(sit_codes)?,
+            _ => check_sit_codes(DEFAULT_SIT_CODES)?,
+        };
+        let (player_id, position) = self.find_player(name)?;
+        let groups = match groups {
+            Some(groups) if !groups.is_empty() => groups.to_vec(),
+            _ => StatGroup::defaults_for_position(&position),
         };
+        let splits = self.api.get_player_splits(player_id, &groups, &sit_codes, &self.season)?;
+
+        let name = &splits.person.full_name;
+        let mut tables = Vec::new();
+        if !splits.hitting.is_empty() {
+            tables.push(split_table(&format!("{} Batting Splits", name), &splits.hitting, hitting::PLAYER)?);
+        }
+        if !splits.pitching.is_empty() {
+            tables.push(split_table(&format!("{} Pitching Splits", name), &splits.pitching, pitching::SPLITS)?);
+        }
+
+        let writer = &mut self.io_reader.writer;
+        if tables.is_empty() && self.format == OutputFormat::Table {
+            writeln!(writer, "No splits for {} in {}", name, self.season)?;
+            return Ok(());
+        }
+        write_output(writer, self.format, &splits, &tables)
+    }
+
+    fn career_table<T>(&self, title: &str, seasons: &[SeasonLine<T>], total: Option<&T>, columns: &[StatCategory]) -> Result<Table, MlbError>
+    where
+        T: Serialize,
+    {
+        let mut records = Vec::new();
+        for line in seasons {
+            // A season split across teams has a line with no team for the whole season, shown like "2TM"
+            let team = match (&line.team, line.num_teams) {
+                (Some(team), _) => self.team_id_map.get(&team.id).cloned().unwrap_or_else(|| team.name.to_string()),
+                (None, Some(num_teams)) => format!("{}TM", num_teams),
+                (None, None) => String::new(),
+            };
+            let mut record = Map::from_iter([("Year".to_string(), Value::from(line.season.as_str())), ("Tm".to_string(), Value::from(team))]);
+            record.extend(stat_record(&line.stat, columns)?);
+            records.push(record);
+        }
+        if let Some(total) = total {
+            let mut record = Map::from_iter([("Year".to_string(), Value::from("Career")), ("Tm".to_string(), Value::from(""))]);
+            record.extend(stat_record(total, columns)?);
+            records.push(record);
+        }
+        Table::from_records(title, &records)
+    }
 
-        let player: Option<Box<dyn Player>> = if let Some(player_value) = player_value {
-            let player_id = player_value["id"].as_u64().unwrap();
+    // Each team's line for a group, plus the season total as "2TM" for a player who changed teams mid-season
+    fn by_team_table<T>(&self, title: &str, lines: &[SeasonLine<T>], total: Option<&T>, columns: &[StatCategory]) -> Result<Table, MlbError>
+    where
+        T: Serialize,
+    {
+        let mut records = Vec::new();
+        for line in lines {
+            let team = line.team.as_ref().map(|team| self.team_id_map.get(&team.id).cloned().unwrap_or_else(|| team.name.to_string()));
+            let mut record = Map::from_iter([("Tm".to_string(), Value::from(team.unwrap_or_default()))]);
+            record.extend(stat_record(&line.stat, columns)?);
+            records.push(record);
+        }
+        if let (Some(total), true) = (total, lines.len() > 1) {
+            let mut record = Map::from_iter([("Tm".to_string(), Value::from(format!("{}TM", lines.len())))]);
+            record.extend(stat_record(total, columns)?);
+            records.push(record);
+        }
+        Table::from_records(title, &records)
+    }
 
-            match player_value["primaryPosition"]["abbreviation"]
-                .as_str()
-                .unwrap()
-            {
-                "P" => Some(Box::new(Pitcher::new(player_id, &self.season))),
-                "TWP" => Some(Box::new(Pitcher::new(player_id, &self.season))), // TODO: fix for shohei
-                _ => Some(Box::new(Batter::new(player_id, &self.season))),
+    fn player_by_team_tables(&self, stats: &PlayerStats, groups: &[StatGroup]) -> Result<Vec<Table>, MlbError> {
+        let name = &stats.person.full_name;
+        let mut tables = Vec::new();
+        for group in groups {
+            match group {
+                StatGroup::Hitting if !stats.hitting_by_team.is_empty() => tables.push(self.by_team_table(
+                    &format!("{} Hitting by Team", name),
+                    &stats.hitting_by_team,
+                    stats.hitting.as_ref(),
+                    hitting::PLAYER,
+                )?),
+                StatGroup::Pitching if !stats.pitching_by_team.is_empty() => tables.push(self.by_team_table(
+                    &format!("{} Pitching by Team", name),
+                    &stats.pitching_by_team,
+                    stats.pitching.as_ref(),
+                    pitching::PLAYER,
+                )?),
+                _ => {}
             }
-        } else {
-            None
+        }
+        Ok(tables)
+    }
+
+    fn write_player_stats(&mut self, stats: &PlayerStats, groups: &[StatGroup], view: PlayerView) -> Result<(), MlbError> {
+        let by_team_tables = match view {
+            PlayerView::ByTeam => self.player_by_team_tables(stats, groups)?,
+            _ => Vec::new(),
         };
+        let name = &stats.person.full_name;
+        let writer = &mut self.io_reader.writer;
 
-        println!("Printing statline for player...");
-        player.unwrap().print_statline();
+        let players: Vec<(StatGroup, Option<&dyn Player>)> = groups
+            .iter()
+            .map(|&group| match group {
+                StatGroup::Hitting => (group, stats.hitting.as_ref().map(|batter| batter as &dyn Player)),
+                StatGroup::Pitching => (group, stats.pitching.as_ref().map(|pitcher| pitcher as &dyn Player)),
+            })
+            .collect();
+        let title = |group: StatGroup| match group {
+            StatGroup::Hitting => format!("{} Hitting", name),
+            StatGroup::Pitching => format!("{} Pitching", name),
+        };
+
+        match (self.format, view) {
+            (OutputFormat::Table, view) => {
+                if !matches!(view, PlayerView::Full(_)) {
+                    writeln!(writer, "Printing statline for player...")?;
+                }
+                for (group, player) in players {
+                    match (player, view) {
+                        (Some(player), PlayerView::Full(mode)) => {
+                            writeln!(writer, "\n{}:", title(group))?;
+                            player.print_full_statline(writer, mode)?;
+                        }
+                        (Some(player), _) => player.print_statline(writer)?,
+                        (None, _) => writeln!(writer, "No {} stats for {} in {}", group, name, self.season)?,
+                    }
+                }
+                if by_team_tables.is_empty() {
+                    return Ok(());
+                }
+                write_output(writer, OutputFormat::Table, stats, &by_team_tables)
+            }
+            (format, PlayerView::ByTeam) => write_output(writer, format, stats, &by_team_tables),
+            (format, PlayerView::Full(_)) => {
+                let mut tables = Vec::new();
+                for (group, player) in players {
+                    if let Some(player) = player {
+                        let rows: Vec<StatlineRow> = player.stat_rows()?.iter().map(StatlineRow::from).collect();
+                        tables.push(Table::from_records(&title(group), &rows)?);
+                    }
+                }
+                write_output(writer, format, stats, &tables)
+            }
+            (format, PlayerView::Statline) => {
+                let mut tables = Vec::new();
+                if let Some(batter) = &stats.hitting {
+                    tables.push(Table::from_records(&title(StatGroup::Hitting), std::slice::from_ref(batter))?);
+                }
+                if let Some(pitcher) = &stats.pitching {
+                    tables.push(Table::from_records(&title(StatGroup::Pitching), std::slice::from_ref(pitcher))?);
+                }
+                write_output(writer, format, stats, &tables)
+            }
+        }
     }
 
-    pub fn get_team_stats(&mut self) {
-        let chosen_team = self.io_reader.get_team_id(&self.team_id_map);
-        let resp = get_team_stats(chosen_team, &self.season);
-        let stats = resp["stats"].as_array().unwrap();
+    pub fn get_team_stats(&mut self) -> Result<(), MlbError> {
+        self.get_team_stats_with(None)
+    }
 
-        let hitting_stats: &Value;
-        let pitching_stats: &Value;
-        if stats[0]["group"]["displayName"].as_str().unwrap().eq("hitting") {
-            hitting_stats = &stats[0]["splits"].as_array().unwrap()[0];
-            pitching_stats = &stats[1]["splits"].as_array().unwrap()[0];
-        } else {
-            pitching_stats = &stats[0]["splits"].as_array().unwrap()[0];
-            hitting_stats = &stats[1]["splits"].as_array().unwrap()[0];
-        }
-        let team_name: &str = hitting_stats["team"]["name"].as_str().unwrap();
-
-        // Print hitting stats
-        println!("\n{} Hitting:", team_name);
-        println!(
-            "{: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10}",
-            "R", "H", "2B", "3B", "HR", "RBI", "SB", "CS", "BB", "HBP", "IBB", "SO", "BA", "OBP", "SLG", "OPS"
-        );
-        println!(
-            "{: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10}",
-            hitting_stats["stat"]["runs"].as_u64().unwrap(),
-            hitting_stats["stat"]["hits"].as_u64().unwrap(),
-            hitting_stats["stat"]["doubles"].as_u64().unwrap(),
-            hitting_stats["stat"]["triples"].as_u64().unwrap(),
-            hitting_stats["stat"]["homeRuns"].as_u64().unwrap(),
-            hitting_stats["stat"]["rbi"].as_u64().unwrap(),
-            hitting_stats["stat"]["stolenBases"].as_u64().unwrap(),
-            hitting_stats["stat"]["caughtStealing"].as_u64().unwrap(),
-            hitting_stats["stat"]["baseOnBalls"].as_u64().unwrap(),
-            hitting_stats["stat"]["hitByPitch"].as_u64().unwrap(),
-            hitting_stats["stat"]["intentionalWalks"].as_u64().unwrap(),
-            hitting_stats["stat"]["strikeOuts"].as_u64().unwrap(),
-            hitting_stats["stat"]["avg"].as_str().unwrap(),
-            hitting_stats["stat"]["obp"].as_str().unwrap(),
-            hitting_stats["stat"]["slg"].as_str().unwrap(),
-            hitting_stats["stat"]["ops"].as_str().unwrap(),
-        );
-
-        // Print pitching stats
-        println!("\n{} Pitching:", team_name);
-        println!(
-            "{: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10}",
-            "W", "L", "W-L%", "ERA", "CG", "SHO", "HLD", "SV", "IP", "HR", "BB", "SO", "HBP", "WHIP", "HR9", "BB9", "SO9", "SO/W"
-        );
-
-        println!(
-            "{: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10} | {: <10}",
-            pitching_stats["stat"]["wins"].as_u64().unwrap(),
-            pitching_stats["stat"]["losses"].as_u64().unwrap(),
-            pitching_stats["stat"]["winPercentage"].as_str().unwrap(),
-            pitching_stats["stat"]["era"].as_str().unwrap(),
-            pitching_stats["stat"]["completeGames"].as_u64().unwrap(),
-            pitching_stats["stat"]["shutouts"].as_u64().unwrap(),
-            pitching_stats["stat"]["holds"].as_u64().unwrap(),
-            pitching_stats["stat"]["saves"].as_u64().unwrap(),
-            pitching_stats["stat"]["inningsPitched"].as_str().unwrap(),
-            pitching_stats["stat"]["homeRuns"].as_u64().unwrap(),
-            pitching_stats["stat"]["baseOnBalls"].as_u64().unwrap(),
-            pitching_stats["stat"]["strikeOuts"].as_u64().unwrap(),
-            pitching_stats["stat"]["hitByPitch"].as_u64().unwrap(),
-            pitching_stats["stat"]["whip"].as_str().unwrap(),
-            pitching_stats["stat"]["homeRunsPer9"].as_str().unwrap(),
-            pitching_stats["stat"]["walksPer9Inn"].as_str().unwrap(),
-            pitching_stats["stat"]["strikeoutsPer9Inn"].as_str().unwrap(),
-            pitching_stats["stat"]["strikeoutWalkRatio"].as_str().unwrap(),
-        );
-    }
-
-    pub fn get_stat_leaders(&mut self) {
-        let stat_type = self.io_reader.get_stat_type();
+    pub fn get_team_stats_with(&mut self, team: Option<&str>) -> Result<(), MlbError> {
+        let chosen_team = self.get_team_id(team)?;
+        let stats = self.api.get_team_stats(chosen_team, &self.season)?;
+
+        let tables = [
+            Table::from_records(&format!("{} Hitting", stats.team.name), &[stat_record(&stats.hitting, hitting::TEAM)?])?,
+            Table::from_records(&format!("{} Pitching", stats.team.name), &[stat_record(&stats.pitching, pitching::TEAM)?])?,
+        ];
+        write_output(&mut self.io_reader.writer, self.format, &stats, &tables)
+    }
+
+    pub fn get_stat_leaders(&mut self) -> Result<(), MlbError> {
+        self.get_stat_leaders_with(None, None, DEFAULT_LEADER_LIMIT)
+    }
+
+    pub fn get_stat_leaders_with(&mut self, stat_type: Option<&str>, category: Option<&str>, limit: usize) -> Result<(), MlbError> {
+        let stat_type = self.get_stat_type(stat_type)?;
         let stat_type = stat_type.as_str();
+        let chosen_category = self.get_leader_category(stat_type, category)?;
 
-        let (leader_categories, stat_categories) = match stat_type {
-            "hitting" => (&self.hitting_leader_categories, HITTING_CATEGORIES),
-            "pitching" => (&self.pitching_leader_categories, PITCHING_CATEGORIES),
-            _ => panic!("Type must be either pitching or hitting")
+        let leader_boards = self.api.get_stat_leaders(&chosen_category, stat_type, &self.season)?;
+        let leaders = match leader_boards.first() {
+            Some(league_leaders) => &league_leaders.leaders,
+            None => return Err(MlbError::MissingField("leagueLeaders".to_string())),
         };
-        let chosen_category = self.io_reader.get_leader_category(leader_categories, stat_categories);
 
-        let resp = get_stat_leaders(&chosen_category, stat_type, &self.season);
-        let leaders: &Vec<Value> = resp["leagueLeaders"].as_array().unwrap()[0]["leaders"].as_array().unwrap();
+        self.write_leaders(&chosen_category, leaders, limit)
+    }
 
-        println!("\nLeaders in {}:", chosen_category);
-        for (index, leader) in leaders.iter().enumerate().filter(|&(i, _)| i < 5 ) {
-            println!("{}) {} ({})", index + 1, leader["person"]["fullName"].as_str().unwrap(), leader["value"].as_str().unwrap());
-        }
+    pub fn get_team_stat_leaders(&mut self) -> Result<(), MlbError> {
+        self.get_team_stat_leaders_with(None, None, None, DEFAULT_LEADER_LIMIT)
     }
 
-    pub fn get_team_stat_leaders(&mut self) {
-        let stat_type = self.io_reader.get_stat_type();
+    pub fn get_team_stat_leaders_with(&mut self, team: Option<&str>, stat_type: Option<&str>, category: Option<&str>, limit: usize) -> Result<(), MlbError> {
+        let stat_type = self.get_stat_type(stat_type)?;
         let stat_type = stat_type.as_str();
 
-        let (leader_categories, stat_categories) = match stat_type {
-            "hitting" => (&self.hitting_leader_categories, HITTING_CATEGORIES),
-            "pitching" => (&self.pitching_leader_categories, PITCHING_CATEGORIES),
-            _ => panic!("Type must be either pitching or hitting")
+        let chosen_team = self.get_team_id(team)?;
+        let chosen_category = self.get_leader_category(stat_type, category)?;
+
+        let leader_boards = self.api.get_team_stat_leaders(chosen_team, &chosen_category, &self.season)?;
+
+        // Team leaders endpoint doesn't support query string for hitting/pitching, do a manual check on response
+        let leaders = match leader_boards.iter().find(|board| board.stat_group.as_deref() == Some(stat_type)) {
+            Some(team_leaders) => &team_leaders.leaders,
+            None => return Err(MlbError::MissingField("teamLeaders".to_string())),
         };
 
-        let chosen_team = self.io_reader.get_team_id(&self.team_id_map);
-        let chosen_category = self.io_reader.get_leader_category(leader_categories, stat_categories);
+        self.write_leaders(&chosen_category, leaders, limit)
+    }
 
-        let resp = get_team_stat_leaders(chosen_team, &chosen_category, &self.season);
+    fn write_leaders(&mut self, category: &str, leaders: &[LeaderEntry], limit: usize) -> Result<(), MlbError> {
+        let leaders = &leaders[..limit.min(leaders.len())];
+        let rows: Vec<LeaderRow> = leaders
+            .iter()
+            .map(|leader| LeaderRow {
+                rank: leader.rank,
+                name: &leader.person.full_name,
+                team: leader.team.as_ref().map(|team| team.name.as_str()).unwrap_or_default(),
+                value: &leader.value,
+            })
+            .collect();
 
-        // Team leaders endpoint doesn't support query string for hitting/pitching, do a manual check on response
-        let leaders: Vec<&Value> = resp["teamLeaders"].as_array().unwrap().iter()
-            .filter(|value| value["statGroup"].as_str().unwrap().eq(stat_type))
+        let writer = &mut self.io_reader.writer;
+        match self.format {
+            OutputFormat::Table => {
+                writeln!(writer, "\nLeaders in {}:", category)?;
+                // Tied leaders share a rank, like the statsapi has them
+                for row in &rows {
+                    writeln!(writer, "{}) {} ({})", row.rank, row.name, row.value)?;
+                }
+                Ok(())
+            }
+            format => {
+                let table = Table::from_records(&format!("Leaders in {}", category), &rows)?;
+                write_output(writer, format, leaders, &[table])
+            }
+        }
+    }
+
+    pub fn get_box_score(&mut self) -> Result<(), MlbError> {
+        self.get_box_score_with(None)
+    }
+
+    pub fn get_box_score_with(&mut self, game_pk: Option<u64>) -> Result<(), MlbError> {
+        let game_pk = match game_pk {
+            Some(game_pk) => game_pk,
+            None => self.io_reader.get_game_pk()?,
+        };
+        let box_score = self.api.get_box_score(game_pk)?;
+
+        let writer = &mut self.io_reader.writer;
+        match self.format {
+            OutputFormat::Table => {
+                box_score.print_box_score(writer)?;
+                Ok(())
+            }
+            format => {
+                let linescore = &box_score.linescore;
+                let line_rows = [(&box_score.away, &linescore.away, false), (&box_score.home, &linescore.home, true)]
+                    .into_iter()
+                    .map(|(team, totals, is_home)| {
+                        let mut row = Map::new();
+                        row.insert("team".to_string(), Value::from(team.team.name.as_str()));
+                        for inning in &linescore.innings {
+                            let runs = if is_home { inning.home } else { inning.away };
+                            row.insert(inning.num.to_string(), runs.map_or(Value::from("x"), Value::from));
+                        }
+                        row.insert("R".to_string(), Value::from(totals.runs));
+                        row.insert("H".to_string(), Value::from(totals.hits));
+                        row.insert("E".to_string(), Value::from(totals.errors));
+                        row
+                    })
+                    .collect::<Vec<Map<String, Value>>>();
+
+                let mut tables = vec![Table::from_records("Line Score", &line_rows)?];
+                for team in [&box_score.away, &box_score.home] {
+                    tables.push(Table::from_records(&format!("{} Batting", team.team.name), &team.batters)?);
+                    tables.push(Table::from_records(&format!("{} Pitching", team.team.name), &team.pitchers)?);
+                }
+                tables.push(Table::from_records("Notes", &box_score.notes)?);
+                write_output(writer, format, &box_score, &tables)
+            }
+        }
+    }
+
+    pub fn get_scoreboard(&mut self) -> Result<(), MlbError> {
+        self.get_scoreboard_with(None, None)
+    }
+
+    pub fn get_scoreboard_with(&mut self, date: Option<&str>, team: Option<&str>) -> Result<(), MlbError> {
+        let date = match date {
+            Some(date) => check_date(date)?,
+            None => self.io_reader.get_date()?,
+        };
+        let mut query = ScheduleQuery::date(date);
+        if let Some(team) = team {
+            query = query.team(self.find_team_id(team)?);
+        }
+
+        let games: Vec<ScheduledGame> = self
+            .api
+            .get_schedule(&query)?
+            .into_iter()
+            .flat_map(|schedule_date| schedule_date.games)
+            .collect();
+
+        let team_name = |team: &Team| -> String { self.team_id_map.get(&team.id).cloned().unwrap_or_else(|| team.name.to_string()) };
+        let probable = |team: &ScheduleTeam| -> String {
+            team.probable_pitcher.as_ref().map(|pitcher| pitcher.full_name.to_string()).unwrap_or_default()
+        };
+        let rows: Vec<ScoreboardRow> = games
+            .iter()
+            .map(|game| {
+                let (away, home) = (&game.teams.away, &game.teams.home);
+                ScoreboardRow {
+                    away: team_name(&away.team),
+                    away_score: away.score,
+                    home: team_name(&home.team),
+                    home_score: home.score,
+                    status: game.status.detailed_state.to_string(),
+                    inning: game.inning().unwrap_or_default(),
+                    away_probable: probable(away),
+                    home_probable: probable(home),
+                }
+            })
+            .collect();
+
+        let writer = &mut self.io_reader.writer;
+        if rows.is_empty() && self.format == OutputFormat::Table {
+            writeln!(writer, "\nNo games on {}", date)?;
+            return Ok(());
+        }
+        let table = Table::from_records(&format!("Scoreboard for {}", date), &rows)?;
+        write_output(writer, self.format, &games, &[table])
+    }
+
+    pub fn get_standings(&mut self) -> Result<(), MlbError> {
+        self.get_standings_with(None, None, false)
+    }
+
+    // Both leagues unless `league` (AL or NL) is given. `date` shows the standings as of that day
+    pub fn get_standings_with(&mut self, league: Option<&str>, date: Option<&str>, wild_card: bool) -> Result<(), MlbError> {
+        let league_ids = match league {
+            Some(league) => vec![check_league(league)?],
+            None => vec![AMERICAN_LEAGUE_ID, NATIONAL_LEAGUE_ID],
+        };
+        let date = date.map(check_date).transpose()?;
+        let standings = self.api.get_standings(&league_ids, &self.season, date)?;
+
+        let row = |team: &TeamStanding| StandingsRow {
+            team: self.team_id_map.get(&team.team.id).cloned().unwrap_or_else(|| team.team.name.to_string()),
+            wins: team.wins,
+            losses: team.losses,
+            winning_percentage: format_pct(team.winning_percentage),
+            games_back: team.games_back.to_string(),
+            wild_card_games_back: team.wild_card_games_back.clone().unwrap_or_default(),
+            streak: team.streak.clone().unwrap_or_default(),
+            last_ten: team.last_ten.map(|record| record.to_string()).unwrap_or_default(),
+            run_differential: team.run_differential,
+            home: team.home.map(|record| record.to_string()).unwrap_or_default(),
+            away: team.away.map(|record| record.to_string()).unwrap_or_default(),
+        };
+
+        let writer = &mut self.io_reader.writer;
+        if wild_card {
+            let races = wild_card_standings(&standings);
+            let tables = races
+                .iter()
+                .map(|race| {
+                    let rows: Vec<StandingsRow> = race.teams.iter().map(row).collect();
+                    Table::from_records(&format!("{} Wild Card", league_name(race.league_id)), &rows)
+                })
+                .collect::<Result<Vec<Table>, MlbError>>()?;
+            write_output(writer, self.format, &races, &tables)
+        } else {
+            let tables = standings
+                .iter()
+                .map(|division| {
+                    let rows: Vec<StandingsRow> = division.teams.iter().map(row).collect();
+                    Table::from_records(division.division.display_name(), &rows)
+                })
+                .collect::<Result<Vec<Table>, MlbError>>()?;
+            write_output(writer, self.format, &standings, &tables)
+        }
+    }
+
+    pub fn get_roster(&mut self) -> Result<(), MlbError> {
+        self.get_roster_with(None, None, None)
+    }
+
+    pub fn get_roster_with(&mut self, team: Option<&str>, roster_type: Option<RosterType>, date: Option<&str>) -> Result<(), MlbError> {
+        let (title, roster) = self.fetch_roster(team, roster_type, date)?;
+        self.write_roster(&title, &roster)
+    }
+
+    // Shows a team's roster, then the statline of a player on it picked by jersey number or name.
+    // Only the table format shows the roster first when the player is given, so other formats stay one document
+    pub fn get_roster_player_with(&mut self, team: Option<&str>, roster_type: Option<RosterType>, date: Option<&str>, player: Option<&str>) -> Result<(), MlbError> {
+        let (title, roster) = self.fetch_roster(team, roster_type, date)?;
+        if player.is_none() || self.format == OutputFormat::Table {
+            self.write_roster(&title, &roster)?;
+        }
+
+        let query = match player {
+            Some(player) => player.to_string(),
+            None => self.io_reader.get_roster_player()?,
+        };
+        let entry = find_roster_player(&roster, &query)?;
+        let groups = StatGroup::defaults_for_position(&entry.position.abbreviation);
+        let stats = PlayerStats::new(&self.api, entry.person.id, &groups, &self.season)?;
+        self.check_player_stats(&stats, &self.season)?;
+        self.write_player_stats(&stats, &groups, PlayerView::Statline)
+    }
+
+    fn fetch_roster(&mut self, team: Option<&str>, roster_type: Option<RosterType>, date: Option<&str>) -> Result<(String, Vec<RosterEntry>), MlbError> {
+        let team_id = self.get_team_id(team)?;
+        let roster_type = match roster_type {
+            Some(roster_type) => roster_type,
+            None => self.io_reader.get_roster_type()?,
+        };
+        let date = date.map(check_date).transpose()?;
+
+        let roster = self.api.get_roster(team_id, roster_type, &self.season, date)?;
+        let team_name = self.team_id_map.get(&team_id).map(String::as_str).unwrap_or_default();
+        Ok((format!("{} {} Roster", team_name, roster_type), roster))
+    }
+
+    fn write_roster(&mut self, title: &str, roster: &[RosterEntry]) -> Result<(), MlbError> {
+        let rows: Vec<RosterRow> = roster
+            .iter()
+            .map(|entry| RosterRow {
+                jersey_number: entry.jersey_number.as_deref().unwrap_or_default(),
+                name: &entry.person.full_name,
+                position: &entry.position.abbreviation,
+                bats_throws: entry.bats_throws(),
+                status: &entry.status.description,
+            })
             .collect();
-        let leaders: &Vec<Value> = leaders[0]["leaders"].as_array().unwrap();
+        let table = Table::from_records(title, &rows)?;
+        write_output(&mut self.io_reader.writer, self.format, &roster, &[table])
+    }
 
-        println!("\nLeaders in {}:", chosen_category);
-        for (index, leader) in leaders.iter().enumerate().filter(|&(i, _)| i < 5 ) {
-            println!("{}) {} ({})", index + 1, leader["person"]["fullName"].as_str().unwrap(), leader["value"].as_str().unwrap());
+    pub fn follow(&mut self) -> Result<(), MlbError> {
+        self.follow_with(None)
+    }
+
+    // Polls a game's live feed until it's final, printing plays as they happen. `game` is either a gamePk or
+    // the abbreviation of a team playing today
+    pub fn follow_with(&mut self, game: Option<&str>) -> Result<(), MlbError> {
+        let game_pk = match game {
+            Some(game) => match game.trim().parse::<u64>() {
+                Ok(game_pk) => game_pk,
+                Err(_) => self.find_todays_game(game)?,
+            },
+            None => self.io_reader.get_game_pk()?,
+        };
+
+        let mut tracker = LiveTracker::new();
+        let mut game_log = Vec::new();
+        loop {
+            let feed = self.api.get_live_feed(game_pk)?;
+            let events = tracker.update(&feed);
+            self.write_live_events(&events)?;
+            game_log.extend(events);
+
+            if tracker.is_finished() {
+                break;
+            }
+            std::thread::sleep(feed.poll_interval());
+        }
+
+        // Tabular formats can't be streamed, so they get the whole game at the end
+        match self.format {
+            format @ (OutputFormat::Csv | OutputFormat::Markdown) => {
+                let table = Table::from_records(&format!("Game {}", game_pk), &game_log)?;
+                write_output(&mut self.io_reader.writer, format, &game_log, &[table])
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn find_todays_game(&self, team: &str) -> Result<u64, MlbError> {
+        let today = chrono::Local::now().naive_local().date();
+        let games: Vec<ScheduledGame> = self
+            .api
+            .get_schedule(&ScheduleQuery::date(today).team(self.find_team_id(team)?))?
+            .into_iter()
+            .flat_map(|schedule_date| schedule_date.games)
+            .collect();
+
+        // Prefer a game that isn't over yet for doubleheaders
+        games
+            .iter()
+            .find(|game| game.status.abstract_game_state != "Final")
+            .or_else(|| games.first())
+            .map(|game| game.game_pk)
+            .ok_or_else(|| MlbError::InvalidInput(format!("{} doesn't play on {}", team, today)))
+    }
+
+    fn write_live_events(&mut self, events: &[LiveEvent]) -> Result<(), MlbError> {
+        let writer = &mut self.io_reader.writer;
+        for event in events {
+            match self.format {
+                OutputFormat::Table => match event.kind {
+                    LiveEventKind::InningStart | LiveEventKind::GameOver => writeln!(writer, "\n{}", event.description)?,
+                    LiveEventKind::ScoringPlay => writeln!(writer, "  Score: {}", event.description)?,
+                    LiveEventKind::PlateAppearance | LiveEventKind::PitchingChange => writeln!(writer, "{}", event.description)?,
+                },
+                OutputFormat::Json => {
+                    serde_json::to_writer(&mut *writer, event)?;
+                    writeln!(writer)?;
+                }
+                OutputFormat::Csv | OutputFormat::Markdown => (),
+            }
         }
+        writer.flush()?;
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+use serde_json::Value;
+
+#[derive(Debug)]
+pub enum MlbError {
+    Transport(reqwest::Error),
+    Status { url: String, status: u16 },
+    Decode(serde_json::Error),
+    MissingField(String),
+    InvalidInput(String),
+    // The player has no stats at all in the season, `seasons` are the ones they do have stats in
+    NoStats { player: String, season: String, seasons: Vec<String> },
+    Io(std::io::Error),
+}
+
+impl fmt::Display for MlbError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MlbError::Transport(e) => write!(f, "Request to statsapi failed: {}", e),
+            MlbError::Status { url, status } => write!(f, "statsapi returned HTTP {} for {}", status, url),
+            MlbError::Decode(e) => write!(f, "Failed to decode statsapi response: {}", e),
+            MlbError::MissingField(field) => write!(f, "statsapi response is missing field \"{}\"", field),
+            MlbError::InvalidInput(msg) => write!(f, "{}", msg),
+            MlbError::NoStats { player, season, seasons } if seasons.is_empty() => {
+                write!(f, "No stats for {} in {}, or any other MLB season", player, season)
+            }
+            MlbError::NoStats { player, season, seasons } => {
+                write!(f, "No stats for {} in {}. Seasons with stats: {}", player, season, seasons.join(", "))
+            }
+            MlbError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MlbError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MlbError::Transport(e) => Some(e),
+            MlbError::Decode(e) => Some(e),
+            MlbError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for MlbError {
+    fn from(e: reqwest::Error) -> Self {
+        MlbError::Transport(e)
+    }
+}
+
+impl From<serde_json::Error> for MlbError {
+    fn from(e: serde_json::Error) -> Self {
+        MlbError::Decode(e)
+    }
+}
+
+impl From<std::io::Error> for MlbError {
+    fn from(e: std::io::Error) -> Self {
+        MlbError::Io(e)
+    }
+}
+
+// Helpers for pulling fields out of raw statsapi JSON without panicking
+pub(crate) fn as_u64(value: &Value, field: &str) -> Result<u64, MlbError> {
+    value[field].as_u64().ok_or_else(|| MlbError::MissingField(field.to_string()))
+}
+
+pub(crate) fn as_str<'a>(value: &'a Value, field: &str) -> Result<&'a str, MlbError> {
+    value[field].as_str().ok_or_else(|| MlbError::MissingField(field.to_string()))
+}
+
+pub(crate) fn as_array<'a>(value: &'a Value, field: &str) -> Result<&'a Vec<Value>, MlbError> {
+    value[field].as_array().ok_or_else(|| MlbError::MissingField(field.to_string()))
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::MlbError;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for OutputFormat {
+    type Err = MlbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "table" => Ok(OutputFormat::Table),
+            "json" => Ok(OutputFormat::Json),
+            "csv" => Ok(OutputFormat::Csv),
+            "markdown" | "md" => Ok(OutputFormat::Markdown),
+            other => Err(MlbError::InvalidInput(format!(
+                "Unknown output format {}, expected table, json, csv or markdown",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputFormat::Table => "table",
+            OutputFormat::Json => "json",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Markdown => "markdown",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Rows of string cells built from serialized records, so every format shows the same data
+pub(crate) struct Table {
+    pub title: String,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl Table {
+    // Each record becomes a row, with the field names of the first record as headers. Fields a later record
+    // leaves out (e.g. one skipped when it's None) are blank
+    pub(crate) fn from_records<T>(title: &str, records: &[T]) -> Result<Self, MlbError> where T: Serialize {
+        let mut headers: Vec<String> = Vec::new();
+        let mut rows = Vec::new();
+
+        for record in records {
+            match serde_json::to_value(record)? {
+                Value::Object(fields) => {
+                    if headers.is_empty() {
+                        headers = fields.keys().cloned().collect();
+                    }
+                    rows.push(headers.iter().map(|header| fields.get(header).map(cell).unwrap_or_default()).collect());
+                }
+                other => return Err(MlbError::InvalidInput(format!("Can't build a table row from {}", other))),
+            }
+        }
+
+        Ok(Table { title: title.to_string(), headers, rows })
+    }
+
+    // The original fixed-width CLI layout, with columns widened past 10 characters when a value needs it
+    pub(crate) fn write_text(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        writeln!(writer, "\n{}:", self.title)?;
+        let widths: Vec<usize> = (0..self.headers.len())
+            .map(|index| {
+                std::iter::once(&self.headers[index])
+                    .chain(self.rows.iter().filter_map(|row| row.get(index)))
+                    .map(|cell| cell.chars().count())
+                    .fold(10, usize::max)
+            })
+            .collect();
+        let line = |cells: &[String]| -> String {
+            cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, &width)| format!("{: <width$}", cell, width = width))
+                .collect::<Vec<String>>()
+                .join(" | ")
+        };
+
+        writeln!(writer, "{}", line(&self.headers))?;
+        for row in &self.rows {
+            writeln!(writer, "{}", line(row))?;
+        }
+        Ok(())
+    }
+
+    fn write_csv(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let escape = |cell: &String| -> String {
+            if cell.contains([',', '"', '\n']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        };
+
+        writeln!(writer, "{}", self.headers.iter().map(escape).collect::<Vec<String>>().join(","))?;
+        for row in &self.rows {
+            writeln!(writer, "{}", row.iter().map(escape).collect::<Vec<String>>().join(","))?;
+        }
+        Ok(())
+    }
+
+    fn write_markdown(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let line = |cells: &[String]| -> String {
+            cells.iter().map(|cell| cell.replace('|', "\\|")).collect::<Vec<String>>().join(" | ")
+        };
+
+        writeln!(writer, "### {}\n", self.title)?;
+        writeln!(writer, "| {} |", line(&self.headers))?;
+        writeln!(writer, "|{}", "---|".repeat(self.headers.len()))?;
+        for row in &self.rows {
+            writeln!(writer, "| {} |", line(row))?;
+        }
+        Ok(())
+    }
+}
+
+// Writes `data` as JSON, or `tables` (which should be built from `data`) as CSV/Markdown/text. Tables built
+// from no records have no columns and are left out
+pub(crate) fn write_output<T>(writer: &mut dyn Write, format: OutputFormat, data: &T, tables: &[Table]) -> Result<(), MlbError>
+where
+    T: Serialize + ?Sized,
+{
+    let tables = tables.iter().filter(|table| !table.headers.is_empty());
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, data)?;
+            writeln!(writer)?;
+        }
+        OutputFormat::Csv => {
+            for (index, table) in tables.enumerate() {
+                if index > 0 {
+                    writeln!(writer)?;
+                }
+                table.write_csv(writer)?;
+            }
+        }
+        OutputFormat::Markdown => {
+            for (index, table) in tables.enumerate() {
+                if index > 0 {
+                    writeln!(writer)?;
+                }
+                table.write_markdown(writer)?;
+            }
+        }
+        OutputFormat::Table => {
+            for table in tables {
+                table.write_text(writer)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use serde::Serialize;
+    use serde_json::json;
+
+    use super::{write_output, OutputFormat, Table};
+
+    #[derive(Serialize)]
+    struct Leader {
+        name: &'static str,
+        value: &'static str,
+    }
+
+    fn leaders() -> Vec<Leader> {
+        vec![
+            Leader { name: "Aaron Judge", value: "62" },
+            Leader { name: "Guerrero, Vladimir", value: "40" },
+        ]
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let table = Table::from_records("Leaders", &leaders()).unwrap();
+        let mut output = Vec::new();
+        write_output(&mut output, format, &leaders(), &[table]).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_csv_escapes_commas() {
+        assert_eq!("name,value\nAaron Judge,62\n\"Guerrero, Vladimir\",40\n", render(OutputFormat::Csv));
+    }
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            "### Leaders\n\n| name | value |\n|---|---|\n| Aaron Judge | 62 |\n| Guerrero, Vladimir | 40 |\n",
+            render(OutputFormat::Markdown)
+        );
+    }
+
+    #[test]
+    fn test_missing_fields_are_blank() {
+        let records = vec![json!({"name": "Giants", "abbreviation": "SF"}), json!({"name": "Athletics"})];
+        let table = Table::from_records("Teams", &records).unwrap();
+        assert_eq!(vec![vec!["Giants", "SF"], vec!["Athletics", ""]], table.rows);
+    }
+
+    #[test]
+    fn test_empty_tables_are_skipped() {
+        let empty: Vec<Leader> = Vec::new();
+        for format in [OutputFormat::Table, OutputFormat::Csv, OutputFormat::Markdown] {
+            let mut output = Vec::new();
+            write_output(&mut output, format, &empty, &[Table::from_records("Leaders", &empty).unwrap()]).unwrap();
+            assert!(output.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_json_keeps_field_order() {
+        assert!(render(OutputFormat::Json).starts_with("[\n  {\n    \"name\": \"Aaron Judge\",\n    \"value\": \"62\""));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(OutputFormat::Markdown, "Markdown".parse().unwrap());
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::error::{as_array, MlbError};
+use crate::player::{Person, StatGroup};
+use crate::team::Team;
+
+// A player's batting line for one game. Rates are left out since they're mostly ".---" over a single game
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GameBatting {
+    pub plate_appearances: u64,
+    pub at_bats: u64,
+    pub runs: u64,
+    pub hits: u64,
+    pub doubles: u64,
+    pub triples: u64,
+    pub home_runs: u64,
+    pub rbi: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub stolen_bases: u64,
+    pub left_on_base: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GamePitching {
+    pub games_started: u64,
+    pub innings_pitched: String,
+    pub hits: u64,
+    pub runs: u64,
+    pub earned_runs: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub home_runs: u64,
+    pub number_of_pitches: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub saves: u64,
+    pub holds: u64,
+    pub blown_saves: u64,
+}
+
+impl GamePitching {
+    // The pitcher's decision the way a box score shows it, e.g. "W" or "BS, L"
+    pub fn decision(&self) -> String {
+        let decisions = [
+            (self.blown_saves, "BS"),
+            (self.holds, "H"),
+            (self.wins, "W"),
+            (self.losses, "L"),
+            (self.saves, "S"),
+        ];
+        decisions
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(_, decision)| *decision)
+            .collect::<Vec<&str>>()
+            .join(", ")
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRef {
+    pub game_pk: u64,
+}
+
+// `is_win` is from the player's team's point of view, and missing for a game that hasn't been decided
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameLogEntry<T> {
+    #[serde_as(as = "DisplayFromStr")]
+    pub date: NaiveDate,
+    pub opponent: Team,
+    pub is_home: bool,
+    pub is_win: Option<bool>,
+    pub game: GameRef,
+    pub stat: T,
+}
+
+impl<T> GameLogEntry<T> {
+    pub fn result(&self) -> &'static str {
+        match self.is_win {
+            Some(true) => "W",
+            Some(false) => "L",
+            None => "",
+        }
+    }
+}
+
+// Games are in the order they were played
+#[derive(Serialize, Debug)]
+pub struct GameLog {
+    pub person: Person,
+    pub hitting: Vec<GameLogEntry<GameBatting>>,
+    pub pitching: Vec<GameLogEntry<GamePitching>>,
+}
+
+fn keep_last<T>(games: &mut Vec<T>, count: usize) {
+    games.drain(..games.len().saturating_sub(count));
+}
+
+impl GameLog {
+    // Only keeps games played between `start` and `end`, inclusive. Either end can be left open
+    pub fn between(mut self, start: Option<NaiveDate>, end: Option<NaiveDate>) -> Self {
+        let in_range = |date: NaiveDate| start.is_none_or(|start| date >= start) && end.is_none_or(|end| date <= end);
+        self.hitting.retain(|game| in_range(game.date));
+        self.pitching.retain(|game| in_range(game.date));
+        self
+    }
+
+    // Only keeps the last `count` games in each group
+    pub fn last(mut self, count: usize) -> Self {
+        keep_last(&mut self.hitting, count);
+        keep_last(&mut self.pitching, count);
+        self
+    }
+}
+
+fn group_games<T>(person: &Value, group: StatGroup) -> Result<Vec<GameLogEntry<T>>, MlbError> where T: DeserializeOwned {
+    let splits = person["stats"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|stats| stats["type"]["displayName"] == "gameLog" && stats["group"]["displayName"] == group.as_str())
+        .and_then(|stats| stats["splits"].as_array());
+
+    match splits {
+        Some(splits) => splits.iter().map(|split| Ok(GameLogEntry::deserialize(split)?)).collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+pub(crate) fn from_game_log(details: Value) -> Result<GameLog, MlbError> {
+    let person = as_array(&details, "people")?
+        .first()
+        .ok_or_else(|| MlbError::MissingField("people".to_string()))?;
+
+    let mut game_log = GameLog {
+        person: Person::deserialize(person)?,
+        hitting: group_games(person, StatGroup::Hitting)?,
+        pitching: group_games(person, StatGroup::Pitching)?,
+    };
+    game_log.hitting.sort_by_key(|game| game.date);
+    game_log.pitching.sort_by_key(|game| game.date);
+    Ok(game_log)
+}
@@ -1,147 +1,268 @@
-use chrono::Datelike;
-use std::{collections::HashMap, io::BufRead};
+use chrono::{Datelike, NaiveDate};
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
 use serde_json::Value;
 
-pub struct IOReader<R> {
+use crate::error::{as_str, MlbError};
+use crate::meta::LeaderCategory;
+use crate::roster::RosterType;
+use crate::standings::{AMERICAN_LEAGUE_ID, NATIONAL_LEAGUE_ID};
+
+// Limit the season to 1871, though from manual testing, MLB only has earliest stats to 1876
+pub fn check_season(season: &str) -> Result<(), MlbError> {
+    let current_year = chrono::Utc::now().year();
+    match season.parse::<i32>() {
+        Ok(num) if num < 1871 || num > current_year => Err(MlbError::InvalidInput(format!(
+            "Year must be between years 1871 and {}",
+            current_year
+        ))),
+        Ok(_) => Ok(()),
+        Err(_) => Err(MlbError::InvalidInput("Not a valid year".to_string())),
+    }
+}
+
+pub fn check_date(date: &str) -> Result<NaiveDate, MlbError> {
+    NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| MlbError::InvalidInput(format!("Date must be in YYYY-MM-DD format, got \"{}\"", date.trim())))
+}
+
+pub fn check_league(league: &str) -> Result<u64, MlbError> {
+    match league.trim().to_uppercase().as_str() {
+        "AL" => Ok(AMERICAN_LEAGUE_ID),
+        "NL" => Ok(NATIONAL_LEAGUE_ID),
+        _ => Err(MlbError::InvalidInput(format!("League must be AL or NL, got \"{}\"", league.trim()))),
+    }
+}
+
+// `groups` are the stat groups with leaderboards, see `StatsMeta::leader_groups`
+pub fn check_stat_type(stat_type: &str, groups: &[String]) -> Result<String, MlbError> {
+    groups
+        .iter()
+        .find(|group| group.eq_ignore_ascii_case(stat_type.trim()))
+        .cloned()
+        .ok_or_else(|| MlbError::InvalidInput(format!(
+            "Stat type must be one of {}, got \"{}\"",
+            groups.join(", "),
+            stat_type.trim()
+        )))
+}
+
+// Prompts are written to `writer` and answers read from `reader`, the binary uses stdout/stdin
+pub struct IOReader<R, W> {
     pub reader: R,
+    pub writer: W,
 }
 
-impl<R> IOReader<R>
+impl<R, W> IOReader<R, W>
 where
-    R: BufRead
+    R: BufRead,
+    W: Write,
 {
-    fn read_input(&mut self, line: &mut String) {
-        self.reader.read_line(line).expect("Failed to read line");
+    fn read_input(&mut self, line: &mut String) -> Result<(), MlbError> {
+        self.reader.read_line(line)?;
+        Ok(())
+    }
+
+    // Reads a 1-indexed menu choice and converts it to an index into a list of the given length
+    fn read_selection(&mut self, len: usize) -> Result<usize, MlbError> {
+        let mut choice = String::new();
+        self.read_input(&mut choice)?;
+
+        match choice.trim().parse::<usize>() {
+            Ok(num) if num >= 1 && num <= len => Ok(num - 1),
+            _ => Err(MlbError::InvalidInput(format!(
+                "Invalid selection \"{}\", expected a number between 1 and {}",
+                choice.trim(),
+                len
+            ))),
+        }
     }
 
-    pub fn get_season(&mut self) -> String {
+    pub fn get_season(&mut self) -> Result<String, MlbError> {
         let current_year = chrono::Utc::now().year();
-        println!("Enter season year (e.g. 2022): ");
+        writeln!(self.writer, "Enter season year (e.g. 2022): ")?;
         let mut season = String::new();
 
         loop {
-            self.read_input(&mut season);
+            self.read_input(&mut season)?;
 
-            // Limit the season to 1871, though from manual testing, MLB only has earliest stats to 1876
             match season.trim() {
                 "" => {
-                    println!("Using default year (this year)");
+                    writeln!(self.writer, "Using default year (this year)")?;
                     season.push_str(&current_year.to_string());
                     break;
                 }
-                season => match season.parse::<i32>() {
-                    Ok(num) => {
-                        if num < 1871 || num > current_year {
-                            println!("Year must be between years 1871 and {}", current_year);
-                        } else {
-                            break;
-                        }
-                    }
-                    Err(_) => println!("Not a valid year"),
+                season => match check_season(season) {
+                    Ok(()) => break,
+                    Err(e) => writeln!(self.writer, "{}", e)?,
                 }
             }
 
             season.clear();
         }
-        season.trim().to_string()
+        Ok(season.trim().to_string())
     }
 
-    pub fn get_name_query(&mut self) -> String {
-        println!("Enter name of player to search: ");
+    pub fn get_name_query(&mut self) -> Result<String, MlbError> {
+        writeln!(self.writer, "Enter name of player to search: ")?;
         let mut name_query = String::new();
-        self.read_input(&mut name_query);
+        self.read_input(&mut name_query)?;
 
-        name_query.trim().to_owned()
+        Ok(name_query.trim().to_owned())
     }
 
-    pub fn get_filtered_players<'a>(&'a mut self, team_id_map: &'a HashMap<u64, String>, filtered_players: &'a Vec<&'a Value>) -> Option<&'a Value> {
-        println!(
+    pub fn get_compare_players(&mut self) -> Result<Vec<String>, MlbError> {
+        writeln!(self.writer, "Enter players to compare, separated by commas (add a year for another season, e.g. Buster Posey 2012): ")?;
+        let mut players = String::new();
+        self.read_input(&mut players)?;
+
+        Ok(players.split(',').map(|player| player.trim().to_owned()).filter(|player| !player.is_empty()).collect())
+    }
+
+    pub fn get_filtered_players<'a>(&mut self, team_id_map: &HashMap<u64, String>, filtered_players: &[&'a Value]) -> Result<&'a Value, MlbError> {
+        writeln!(
+            self.writer,
             "{} players found, select the player to view stats for (pick a number).",
             filtered_players.len()
-        );
+        )?;
 
         for (index, player) in filtered_players.iter().enumerate() {
-            println!(
+            let team = player["currentTeam"]["id"].as_u64().and_then(|id| team_id_map.get(&id));
+            writeln!(
+                self.writer,
                 "{}) {}, {} ({})",
                 index + 1,
-                player["fullName"].as_str().unwrap(),
-                team_id_map[&player["currentTeam"]["id"].as_u64().unwrap()],
-                player["primaryPosition"]["abbreviation"].as_str().unwrap()
-            );
+                as_str(player, "fullName")?,
+                team.map(String::as_str).unwrap_or("-"),
+                as_str(&player["primaryPosition"], "abbreviation")?
+            )?;
         }
 
-        let mut chosen_player = String::new();
-        self.read_input(&mut chosen_player);
-
-        Some(filtered_players[chosen_player.trim().parse::<usize>().unwrap() - 1])
+        let index = self.read_selection(filtered_players.len())?;
+        Ok(filtered_players[index])
     }
 
-    pub fn get_team_id(&mut self, team_id_map: &HashMap<u64, String>) -> u64 {
-        println!("Select a team:");
+    pub fn get_team_id(&mut self, team_id_map: &HashMap<u64, String>) -> Result<u64, MlbError> {
+        writeln!(self.writer, "Select a team:")?;
 
         let mut team_ids: Vec<&u64> = Vec::new();
         for (index, (key, value)) in team_id_map.iter().enumerate() {
-            print!("{}) {: <10}", index + 1, value);
-            print!("{}", if index  % 5 == 4 { "\n" } else { "\t" });
+            write!(self.writer, "{}) {: <10}", index + 1, value)?;
+            write!(self.writer, "{}", if index  % 5 == 4 { "\n" } else { "\t" })?;
 
             team_ids.push(key);
         }
 
-        let mut chosen_team = String::new();
-        self.read_input(&mut chosen_team);
+        let index = self.read_selection(team_ids.len())?;
+        Ok(team_ids[index].to_owned())
+    }
+
+    pub fn get_leader_category(&mut self, categories: &[LeaderCategory]) -> Result<LeaderCategory, MlbError> {
+        writeln!(self.writer, "Select a leader category:")?;
+        for (index, category) in categories.iter().enumerate() {
+            writeln!(self.writer, "{}) {: <10} {}", index + 1, category.label(), category.description)?;
+        }
+
+        let index = self.read_selection(categories.len())?;
+        Ok(categories[index].clone())
+    }
+
+    pub fn get_game_pk(&mut self) -> Result<u64, MlbError> {
+        writeln!(self.writer, "Enter game id (gamePk): ")?;
+        let mut game_pk = String::new();
+        self.read_input(&mut game_pk)?;
 
-        team_ids[chosen_team.trim().parse::<usize>().unwrap() - 1].to_owned()
+        game_pk
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| MlbError::InvalidInput(format!("Not a valid game id \"{}\"", game_pk.trim())))
     }
 
-    pub fn get_leader_category<'a>(&'a mut self, leader_categories: &'a HashMap<&'a str, &'a str>, stat_categories: &'a [&'a str]) -> &'a str {
-        println!("Select a leader category:");
-        for (index, category) in stat_categories.iter().enumerate() {
-            print!("{}) {: <10}", index + 1, category);
-            print!("{}", if index % 3 == 2 || index == stat_categories.len() { "\n" } else { "\t" });
+    pub fn get_date(&mut self) -> Result<NaiveDate, MlbError> {
+        writeln!(self.writer, "Enter date (YYYY-MM-DD, blank for today): ")?;
+        let mut date = String::new();
+        self.read_input(&mut date)?;
+
+        match date.trim() {
+            "" => Ok(chrono::Local::now().naive_local().date()),
+            date => check_date(date),
+        }
+    }
+
+    pub fn get_roster_type(&mut self) -> Result<RosterType, MlbError> {
+        writeln!(self.writer, "Enter roster type (active, 40Man, fullSeason or depthChart, blank for active): ")?;
+        let mut roster_type = String::new();
+        self.read_input(&mut roster_type)?;
+
+        match roster_type.trim() {
+            "" => Ok(RosterType::Active),
+            roster_type => roster_type.parse(),
         }
+    }
+
+    pub fn get_roster_player(&mut self) -> Result<String, MlbError> {
+        writeln!(self.writer, "Enter jersey number or name of a player on the roster: ")?;
+        let mut player = String::new();
+        self.read_input(&mut player)?;
 
-        let mut chosen_category = String::new();
-        self.read_input(&mut chosen_category);
-        let chosen_category = stat_categories[chosen_category.trim().parse::<usize>().unwrap() - 1].to_owned();
-        leader_categories[chosen_category.as_str()]
+        Ok(player.trim().to_owned())
     }
 
-    pub fn get_stat_type(&mut self) -> String {
-        println!("Enter stat type for leaders (hitting or pitching): ");
+    pub fn get_stat_type(&mut self, groups: &[String]) -> Result<String, MlbError> {
+        writeln!(self.writer, "Enter stat type for leaders ({}): ", groups.join(", "))?;
         let mut stat_type = String::new();
-        self.read_input(&mut stat_type);
+        self.read_input(&mut stat_type)?;
 
-        stat_type.trim().to_owned()
+        check_stat_type(&stat_type, groups)
     }
 }
 
 // TODO: look into parameterized testing options, rust macros don't feel good enough to use
 #[cfg(test)]
 mod test {
-    use super::IOReader;
+    use super::{check_season, IOReader};
 
     #[test]
     fn test_get_season_success() {
         let input = "2022\n".as_bytes();
-        let mut io_reader = IOReader { reader: input };
+        let mut io_reader = IOReader { reader: input, writer: Vec::new() };
 
-        assert_eq!("2022", io_reader.get_season());
+        assert_eq!("2022", io_reader.get_season().unwrap());
     }
 
     #[test]
     fn test_get_season_retry() {
         let input = "1850\n2099\n2010\n".as_bytes();
-        let mut io_reader = IOReader { reader: input };
+        let mut io_reader = IOReader { reader: input, writer: Vec::new() };
+
+        assert_eq!("2010", io_reader.get_season().unwrap());
+
+        let output = String::from_utf8(io_reader.writer).unwrap();
+        assert_eq!(2, output.matches("Year must be between years 1871 and").count());
+    }
 
-        assert_eq!("2010", io_reader.get_season());
+    #[test]
+    fn test_check_season() {
+        assert!(check_season("2022").is_ok());
+        assert!(check_season("1850").is_err());
+        assert!(check_season("twenty").is_err());
     }
 
     #[test]
     fn test_get_name_query() {
         let input = "Alvarez\n".as_bytes();
-        let mut io_reader = IOReader { reader: input };
+        let mut io_reader = IOReader { reader: input, writer: Vec::new() };
+
+        assert_eq!("Alvarez", io_reader.get_name_query().unwrap());
+        assert_eq!("Enter name of player to search: \n", String::from_utf8(io_reader.writer).unwrap());
+    }
+
+    #[test]
+    fn test_get_stat_type_invalid() {
+        let input = "fielding\n".as_bytes();
+        let mut io_reader = IOReader { reader: input, writer: Vec::new() };
 
-        assert_eq!("Alvarez", io_reader.get_name_query());
+        assert!(io_reader.get_stat_type(&["hitting".to_string(), "pitching".to_string()]).is_err());
     }
 
     // NOTE: Could add more testing for functions here. Given the nature of these funcs though, it's a lot of setup
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::player::Person;
+use crate::team::Team;
+
+// Values are kept as the API formats them (e.g. ".311" or "1.75")
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderEntry {
+    pub rank: u64,
+    pub person: Person,
+    pub team: Option<Team>,
+    pub value: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderBoard {
+    pub leader_category: String,
+    pub stat_group: Option<String>,
+    #[serde(default)]
+    pub leaders: Vec<LeaderEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LeagueLeadersResponse {
+    league_leaders: Vec<LeaderBoard>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TeamLeadersResponse {
+    team_leaders: Vec<LeaderBoard>,
+}
+
+pub(crate) fn from_league_leaders(resp: Value) -> Result<Vec<LeaderBoard>, MlbError> {
+    Ok(serde_json::from_value::<LeagueLeadersResponse>(resp)?.league_leaders)
+}
+
+pub(crate) fn from_team_leaders(resp: Value) -> Result<Vec<LeaderBoard>, MlbError> {
+    Ok(serde_json::from_value::<TeamLeadersResponse>(resp)?.team_leaders)
+}
@@ -1,8 +1,64 @@
 use std::io::BufRead;
 
+#[cfg(feature = "async")]
+mod async_client;
+mod boxscore;
+mod cache;
+mod career;
+mod category;
 mod client;
+mod error;
+mod format;
+mod gamelog;
 mod io;
+mod leaders;
+mod live;
+mod meta;
 mod player;
+mod record;
 mod requests;
+mod roster;
+mod schedule;
+mod search;
+mod splits;
+mod standings;
+mod team;
+mod transport;
 
-pub fn create_client<R>(reader: R) -> client::MlbClient<'static, R> where R: BufRead { client::MlbClient::new(reader) }
\ No newline at end of file
+#[cfg(feature = "async")]
+pub use async_client::{AsyncHttpTransport, AsyncMlbClient, AsyncTransport, BoxFuture};
+pub use boxscore::{
+    BattingLine, BoxNote, BoxScore, BoxScoreBatting, BoxScorePitching, InfoSection, InningRuns, LineScore, LineScoreTotals,
+    PitchingLine, TeamBoxScore,
+};
+pub use career::{AdvancedBatter, AdvancedPitcher, Career, PlayerCareer, SeasonLine};
+pub use cache::{clear_cache, default_cache_dir, CachingTransport};
+pub use category::{categories, hitting, pitching, StatCategory};
+pub use client::{MlbClient, MlbClientBuilder, DEFAULT_LEADER_LIMIT};
+pub use error::MlbError;
+pub use format::OutputFormat;
+pub use gamelog::{GameBatting, GameLog, GameLogEntry, GamePitching, GameRef};
+pub use leaders::{LeaderBoard, LeaderEntry};
+pub use live::{
+    FeedGameData, FeedMetaData, FeedTeams, LiveData, LiveEvent, LiveEventKind, LiveFeed, LiveTracker, Matchup, Play, PlayAbout,
+    PlayEvent, PlayEventDetails, PlayResult, Plays,
+};
+pub use meta::{BaseballStat, LeaderCategory, MetaType, StatsMeta};
+pub use player::{Batter, Person, Pitcher, Player, PlayerStats, StatGroup, StatRow, StatlineMode};
+pub use record::{RecordingTransport, ReplayTransport};
+pub use requests::{StatsApi, MLB_LOOKUP_API_ENDPOINT};
+pub use reqwest::Url;
+pub use roster::{find_roster_player, Hand, Position, RosterEntry, RosterPerson, RosterStatus, RosterType};
+pub use schedule::{GameInning, GameStatus, ScheduleDate, ScheduleQuery, ScheduleTeam, ScheduleTeams, ScheduledGame};
+pub use search::{exact_match, match_score, normalize_name, rank_players, search_players};
+pub use splits::{
+    check_sit_codes, PlayerSplits, Situation, SituationLine, DAY_NIGHT, DEFAULT_SIT_CODES, HOME_AWAY, MONTHS, PLATOON, RISP,
+};
+pub use standings::{
+    league_name, wild_card_standings, Division, DivisionStandings, TeamStanding, WildCardStandings, WinLoss, AMERICAN_LEAGUE_ID,
+    NATIONAL_LEAGUE_ID,
+};
+pub use team::{Team, TeamHittingStats, TeamPitchingStats, TeamStats};
+pub use transport::{HttpTransport, Transport};
+
+pub fn create_client<R>(reader: R) -> Result<MlbClient<R>, MlbError> where R: BufRead { MlbClient::new(reader) }
@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::player::Person;
+use crate::schedule::GameStatus;
+use crate::team::Team;
+
+// Used when the feed doesn't say how long to wait before polling again
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct FeedMetaData {
+    pub wait: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FeedTeams {
+    pub away: Team,
+    pub home: Team,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct FeedGameData {
+    pub status: GameStatus,
+    pub teams: FeedTeams,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PlayResult {
+    pub event: Option<String>,
+    pub event_type: Option<String>,
+    pub description: Option<String>,
+    pub rbi: u64,
+    pub away_score: u64,
+    pub home_score: u64,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayAbout {
+    pub at_bat_index: u64,
+    pub half_inning: String,
+    pub inning: u64,
+    #[serde(default)]
+    pub is_complete: bool,
+    #[serde(default)]
+    pub is_scoring_play: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Matchup {
+    pub batter: Person,
+    pub pitcher: Person,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PlayEventDetails {
+    pub description: Option<String>,
+    pub event_type: Option<String>,
+}
+
+// A pitch, pickoff or action (e.g. a substitution) within a plate appearance
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayEvent {
+    pub index: u64,
+    #[serde(default)]
+    pub details: PlayEventDetails,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Play {
+    #[serde(default)]
+    pub result: PlayResult,
+    pub about: PlayAbout,
+    pub matchup: Matchup,
+    #[serde(default)]
+    pub play_events: Vec<PlayEvent>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Plays {
+    pub all_plays: Vec<Play>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LiveData {
+    #[serde(default)]
+    pub plays: Plays,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveFeed {
+    pub game_pk: u64,
+    #[serde(default)]
+    pub meta_data: FeedMetaData,
+    pub game_data: FeedGameData,
+    pub live_data: LiveData,
+}
+
+impl LiveFeed {
+    pub fn is_final(&self) -> bool {
+        self.game_data.status.abstract_game_state == "Final"
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.meta_data.wait.map_or(DEFAULT_POLL_INTERVAL, Duration::from_secs)
+    }
+}
+
+pub(crate) fn from_live_feed(resp: Value) -> Result<LiveFeed, MlbError> {
+    Ok(serde_json::from_value(resp)?)
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveEventKind {
+    InningStart,
+    PlateAppearance,
+    ScoringPlay,
+    PitchingChange,
+    GameOver,
+}
+
+// Every event has the same fields so a game's events can be written as one table
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveEvent {
+    pub kind: LiveEventKind,
+    pub inning: u64,
+    pub half_inning: String,
+    pub description: String,
+    pub away_score: u64,
+    pub home_score: u64,
+}
+
+// Remembers what's been seen across polls of the same game, so each snapshot only yields what's new
+#[derive(Debug, Default)]
+pub struct LiveTracker {
+    half_innings: HashSet<(u64, String)>,
+    plate_appearances: HashSet<u64>,
+    play_events: HashSet<(u64, u64)>,
+    score: (u64, u64),
+    finished: bool,
+}
+
+impl LiveTracker {
+    pub fn new() -> Self {
+        LiveTracker::default()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn update(&mut self, feed: &LiveFeed) -> Vec<LiveEvent> {
+        let mut events = Vec::new();
+        let teams = &feed.game_data.teams;
+        let short_name = |team: &Team| team.abbreviation.as_deref().unwrap_or(&team.name).to_string();
+
+        for play in &feed.live_data.plays.all_plays {
+            let about = &play.about;
+            let event = |kind: LiveEventKind, description: String, score: (u64, u64)| LiveEvent {
+                kind,
+                inning: about.inning,
+                half_inning: about.half_inning.to_string(),
+                description,
+                away_score: score.0,
+                home_score: score.1,
+            };
+
+            if self.half_innings.insert((about.inning, about.half_inning.to_string())) {
+                let half = match about.half_inning.as_str() {
+                    "top" => "Top",
+                    "bottom" => "Bottom",
+                    other => other,
+                };
+                events.push(event(LiveEventKind::InningStart, format!("{} {}", half, ordinal(about.inning)), self.score));
+            }
+
+            for play_event in &play.play_events {
+                let details = &play_event.details;
+                if details.event_type.as_deref() == Some("pitching_substitution")
+                    && self.play_events.insert((about.at_bat_index, play_event.index))
+                {
+                    let description = details.description.as_deref().unwrap_or("Pitching change").to_string();
+                    events.push(event(LiveEventKind::PitchingChange, description, self.score));
+                }
+            }
+
+            if about.is_complete && self.plate_appearances.insert(about.at_bat_index) {
+                let result = &play.result;
+                self.score = (result.away_score, result.home_score);
+                let description = result
+                    .description
+                    .as_deref()
+                    .or(result.event.as_deref())
+                    .unwrap_or_default()
+                    .to_string();
+                events.push(event(LiveEventKind::PlateAppearance, description, self.score));
+
+                if about.is_scoring_play {
+                    let score = format!("{} {}, {} {}", short_name(&teams.away), self.score.0, short_name(&teams.home), self.score.1);
+                    events.push(event(LiveEventKind::ScoringPlay, score, self.score));
+                }
+            }
+        }
+
+        if feed.is_final() && !self.finished {
+            self.finished = true;
+            let last = feed.live_data.plays.all_plays.last();
+            events.push(LiveEvent {
+                kind: LiveEventKind::GameOver,
+                inning: last.map_or(0, |play| play.about.inning),
+                half_inning: last.map(|play| play.about.half_inning.to_string()).unwrap_or_default(),
+                description: format!(
+                    "{}: {} {}, {} {}",
+                    feed.game_data.status.detailed_state,
+                    short_name(&teams.away),
+                    self.score.0,
+                    short_name(&teams.home),
+                    self.score.1
+                ),
+                away_score: self.score.0,
+                home_score: self.score.1,
+            });
+        }
+        events
+    }
+}
+
+fn ordinal(num: u64) -> String {
+    let suffix = match (num % 10, num % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", num, suffix)
+}
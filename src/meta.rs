@@ -0,0 +1,216 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::category::{categories, StatCategory};
+use crate::error::MlbError;
+use crate::player::StatGroup;
+
+// An entry of the statsapi's `leagueLeaderTypes` or `statGroups` lists
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MetaType {
+    pub display_name: String,
+}
+
+// An entry of the statsapi's `baseballStats` list, e.g. homeRuns ("home runs") in hitting and pitching
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseballStat {
+    pub name: String,
+    pub lookup_param: Option<String>,
+    pub label: Option<String>,
+    #[serde(default)]
+    pub is_counting: bool,
+    #[serde(default)]
+    pub stat_groups: Vec<MetaType>,
+}
+
+// A leaderboard the statsapi has, e.g. doubles. `abbreviation` is the registry's label for it (2B) if there is one
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LeaderCategory {
+    pub name: String,
+    pub abbreviation: Option<&'static str>,
+    pub description: String,
+    pub groups: Vec<String>,
+}
+
+impl LeaderCategory {
+    // What it's listed as, the abbreviation if it has one
+    pub fn label(&self) -> &str {
+        self.abbreviation.unwrap_or(&self.name)
+    }
+
+    // Matches the abbreviation (e.g. HR) or the statsapi's name for it (e.g. homeRuns)
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.trim();
+        self.name.eq_ignore_ascii_case(name) || self.abbreviation.is_some_and(|abbreviation| abbreviation.eq_ignore_ascii_case(name))
+    }
+
+    pub fn in_group(&self, group: &str) -> bool {
+        self.groups.iter().any(|other| other.eq_ignore_ascii_case(group))
+    }
+}
+
+// The statsapi's lists of leader categories, stat groups and stats
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsMeta {
+    pub leader_types: Vec<MetaType>,
+    pub stat_groups: Vec<MetaType>,
+    pub baseball_stats: Vec<BaseballStat>,
+}
+
+pub(crate) fn from_stats_meta(leader_types: Value, stat_groups: Value, baseball_stats: Value) -> Result<StatsMeta, MlbError> {
+    Ok(StatsMeta {
+        leader_types: serde_json::from_value(leader_types)?,
+        stat_groups: serde_json::from_value(stat_groups)?,
+        baseball_stats: serde_json::from_value(baseball_stats)?,
+    })
+}
+
+// "gamesFinished" becomes "Games finished", for categories nothing else describes
+fn describe(name: &str) -> String {
+    let mut description = String::new();
+    for (index, c) in name.chars().enumerate() {
+        match (index, c) {
+            (0, c) => description.extend(c.to_uppercase()),
+            (_, c) if c.is_ascii_uppercase() => {
+                description.push(' ');
+                description.push(c.to_ascii_lowercase());
+            }
+            (_, c) if c.is_ascii_digit() && !description.ends_with(|last: char| last.is_ascii_digit()) => {
+                description.push(' ');
+                description.push(c);
+            }
+            (_, c) => description.push(c),
+        }
+    }
+    description
+}
+
+// Registry stats without a leader name of their own are matched on their field, e.g. doubles
+fn leads(category: &StatCategory, name: &str) -> bool {
+    category.leader_name.unwrap_or(category.field).eq_ignore_ascii_case(name)
+}
+
+fn registry_categories(name: &str) -> Vec<&'static StatCategory> {
+    [StatGroup::Hitting, StatGroup::Pitching]
+        .into_iter()
+        .flat_map(categories)
+        .filter(|category| leads(category, name))
+        .collect()
+}
+
+impl StatsMeta {
+    // Every leaderboard the statsapi has in a group, the registry's leader categories first and the rest in the API's order.
+    // Groups come from the registry or `baseballStats`, so a leader type neither knows about isn't offered
+    pub fn leader_categories(&self, group: &str) -> Vec<LeaderCategory> {
+        let mut leader_categories: Vec<LeaderCategory> = self
+            .leader_types
+            .iter()
+            .map(|leader_type| self.leader_category(&leader_type.display_name, group))
+            .filter(|category| category.in_group(group))
+            .collect();
+
+        let registry_index = |category: &LeaderCategory| {
+            let group = group.parse::<StatGroup>().ok()?;
+            categories(group).iter().position(|registry| registry.leader_name == Some(category.name.as_str()))
+        };
+        leader_categories.sort_by_key(|category| registry_index(category).unwrap_or(usize::MAX));
+        leader_categories
+    }
+
+    fn leader_category(&self, name: &str, group: &str) -> LeaderCategory {
+        let registry = registry_categories(name);
+        let stat = self.baseball_stats.iter().find(|stat| stat.name.eq_ignore_ascii_case(name));
+
+        let mut groups: Vec<String> = registry.iter().map(|category| category.group.as_str().to_string()).collect();
+        for stat_group in stat.into_iter().flat_map(|stat| &stat.stat_groups) {
+            if !groups.iter().any(|other| other.eq_ignore_ascii_case(&stat_group.display_name)) {
+                groups.push(stat_group.display_name.clone());
+            }
+        }
+
+        // Registry labels are per group, e.g. homeRuns is "Home runs" for hitters and "Home runs allowed" for pitchers
+        let registry = registry
+            .iter()
+            .find(|category| category.group.as_str().eq_ignore_ascii_case(group))
+            .or_else(|| registry.first());
+        let description = match (registry, stat.and_then(|stat| stat.label.as_deref())) {
+            (Some(category), _) => category.description.to_string(),
+            (None, Some(label)) => describe_label(label),
+            (None, None) => describe(name),
+        };
+
+        LeaderCategory { name: name.to_string(), abbreviation: registry.map(|category| category.abbreviation), description, groups }
+    }
+
+    pub fn find_leader_category(&self, group: &str, name: &str) -> Option<LeaderCategory> {
+        self.leader_categories(group).into_iter().find(|category| category.matches(name))
+    }
+
+    // The stat groups with at least one leaderboard, e.g. hitting, pitching and fielding
+    pub fn leader_groups(&self) -> Vec<String> {
+        self.stat_groups
+            .iter()
+            .map(|group| group.display_name.clone())
+            .filter(|group| !self.leader_categories(group).is_empty())
+            .collect()
+    }
+}
+
+// The statsapi's labels are lowercase, e.g. "home runs"
+fn describe_label(label: &str) -> String {
+    let mut chars = label.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn stats_meta() -> StatsMeta {
+        from_stats_meta(
+            json!([{"displayName": "homeRuns"}, {"displayName": "doubles"}, {"displayName": "assists"}, {"displayName": "gamesFinished"}, {"displayName": "mystery"}]),
+            json!([{"displayName": "hitting"}, {"displayName": "pitching"}, {"displayName": "fielding"}, {"displayName": "streak"}]),
+            json!([
+                {"name": "doubles", "lookupParam": "d", "isCounting": true, "label": "doubles", "statGroups": [{"displayName": "hitting"}, {"displayName": "pitching"}]},
+                {"name": "assists", "lookupParam": "a", "isCounting": true, "label": "assists", "statGroups": [{"displayName": "fielding"}]},
+                {"name": "gamesFinished", "isCounting": true, "statGroups": [{"displayName": "pitching"}]}
+            ]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_leader_categories() {
+        let meta = stats_meta();
+        let hitting = meta.leader_categories("hitting");
+        assert_eq!(vec!["HR", "2B"], hitting.iter().map(LeaderCategory::label).collect::<Vec<&str>>());
+        assert_eq!(("Home runs", "Doubles"), (hitting[0].description.as_str(), hitting[1].description.as_str()));
+
+        let pitching = meta.leader_categories("pitching");
+        assert_eq!(("Home runs allowed", "Games finished"), (pitching[0].description.as_str(), pitching[2].description.as_str()));
+        assert_eq!(vec!["assists"], meta.leader_categories("fielding").iter().map(LeaderCategory::label).collect::<Vec<&str>>());
+        assert_eq!(vec!["hitting", "pitching", "fielding"], meta.leader_groups());
+    }
+
+    #[test]
+    fn test_find_leader_category() {
+        let meta = stats_meta();
+        assert_eq!(Some("homeRuns".to_string()), meta.find_leader_category("pitching", "hr").map(|category| category.name));
+        assert_eq!(Some("doubles".to_string()), meta.find_leader_category("hitting", "Doubles").map(|category| category.name));
+        assert_eq!(None, meta.find_leader_category("hitting", "assists"));
+        assert_eq!(None, meta.find_leader_category("hitting", "mystery"));
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!("Games finished", describe("gamesFinished"));
+        assert_eq!("Strikeouts per 9 inn", describe("strikeoutsPer9Inn"));
+    }
+}
@@ -1,106 +1,418 @@
-use serde::Deserialize;
-use serde_with::{serde_as, DisplayFromStr};
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DefaultOnError, DisplayFromStr};
 
-use crate::requests::get_player_details;
+use crate::career::SeasonLine;
+use crate::category::{hitting, pitching, StatCategory};
+use crate::error::{as_array, MlbError};
+use crate::requests::StatsApi;
+
+// One labeled stat, e.g. OBP (On-base percentage) .356. `precision` is the number of decimals it's shown with,
+// 0 for counting stats
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct StatRow {
+    pub label: &'static str,
+    pub description: &'static str,
+    pub value: Option<f64>,
+    pub precision: usize,
+}
+
+impl StatRow {
+    fn new(category: &StatCategory, value: Option<f64>) -> Self {
+        StatRow { label: category.abbreviation, description: category.description, value, precision: category.precision }
+    }
+
+    // Shown the way a box score does: ".273" rather than "0.273", and the statsapi's placeholder for no value
+    pub fn display_value(&self) -> String {
+        StatCategory::format_value(self.value, self.precision)
+    }
+}
+
+// Rows for each of the categories, read out of a serialized stat group
+fn stat_rows<T>(stats: &T, categories: &[StatCategory]) -> Result<Vec<StatRow>, MlbError> where T: Serialize {
+    let stats = serde_json::to_value(stats)?;
+    Ok(categories.iter().map(|category| StatRow::new(category, category.value(&stats))).collect())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum StatlineMode {
+    // One stat per line with its description
+    #[default]
+    Standard,
+    // Every stat on a single line
+    Compact,
+}
 
 pub trait Player: std::fmt::Debug {
-    fn print_statline(&self);
+    // Every stat the statline has, in display order
+    fn stat_rows(&self) -> Result<Vec<StatRow>, MlbError>;
+
+    // The short summary, a slash line for hitters and W-L and ERA for pitchers
+    fn print_statline(&self, writer: &mut dyn Write) -> std::io::Result<()>;
+
+    fn print_full_statline(&self, writer: &mut dyn Write, mode: StatlineMode) -> Result<(), MlbError> {
+        let rows = self.stat_rows()?;
+        match mode {
+            StatlineMode::Standard => {
+                let label_width = rows.iter().map(|row| row.label.len()).max().unwrap_or_default();
+                let value_width = rows.iter().map(|row| row.display_value().len()).max().unwrap_or_default();
+                for row in &rows {
+                    writeln!(writer, "{:<label_width$}  {:>value_width$}  {}", row.label, row.display_value(), row.description)?;
+                }
+                Ok(())
+            }
+            StatlineMode::Compact => {
+                let stats: Vec<String> = rows.iter().map(|row| format!("{} {}", row.label, row.display_value())).collect();
+                writeln!(writer, "{}", stats.join(" | "))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatGroup {
+    Hitting,
+    Pitching,
 }
 
+impl StatGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatGroup::Hitting => "hitting",
+            StatGroup::Pitching => "pitching",
+        }
+    }
+
+    // Groups shown for a player when none are asked for, two-way players get both
+    pub fn defaults_for_position(position: &str) -> Vec<StatGroup> {
+        match position {
+            // Depth charts split pitchers into starters, relievers and the closer
+            "P" | "SP" | "RP" | "CP" => vec![StatGroup::Pitching],
+            "TWP" => vec![StatGroup::Hitting, StatGroup::Pitching],
+            _ => vec![StatGroup::Hitting],
+        }
+    }
+}
+
+impl FromStr for StatGroup {
+    type Err = MlbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "hitting" => Ok(StatGroup::Hitting),
+            "pitching" => Ok(StatGroup::Pitching),
+            other => Err(MlbError::InvalidInput(format!(
+                "Stat type must be either hitting or pitching, got \"{}\"",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for StatGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub id: u64,
+    pub full_name: String,
+}
+
+// Rates are None where the statsapi has a placeholder instead of a number, like ".---" for a batting
+// average with no at bats or "-.--" for an ERA with no innings pitched
 #[serde_as]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct Batter {
-    gamesPlayed: u64,
-    plateAppearances: u64,
-    atBats: u64,
-    runs: u64,
-    hits: u64,
-    doubles: u64,
-    triples: u64,
-    homeRuns: u64,
-    rbi: u64,
-    stolenBases: u64,
-    caughtStealing: u64,
-    baseOnBalls: u64,
-    hitByPitch: u64,
-    strikeOuts: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    avg: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    babip: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    obp: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    slg: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    ops: f64,
+    pub games_played: u64,
+    pub plate_appearances: u64,
+    pub at_bats: u64,
+    pub runs: u64,
+    pub hits: u64,
+    pub doubles: u64,
+    pub triples: u64,
+    pub home_runs: u64,
+    pub rbi: u64,
+    pub stolen_bases: u64,
+    pub caught_stealing: u64,
+    pub base_on_balls: u64,
+    pub hit_by_pitch: u64,
+    pub strike_outs: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub avg: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub babip: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub obp: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub slg: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub ops: Option<f64>,
 }
 
 #[serde_as]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
 pub struct Pitcher {
-    wins: u64,
-    losses: u64,
-
-    // TODO: this is expressed as ".---" for players with 0 W/ 0L
-    // #[serde_as(as = "DisplayFromStr")]
-    // winPercentage: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    era: f64,
-    gamesPitched: u64,
-    gamesStarted: u64,
-    gamesFinished: u64,
-    completeGames: u64,
-    shutouts: u64,
-    holds: u64,
-    saves: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    inningsPitched: f64,
-    hits: u64,
-    runs: u64,
-    earnedRuns: u64,
-    homeRuns: u64,
-    baseOnBalls: u64,
-    strikeOuts: u64,
-    hitByPitch: u64,
-    #[serde_as(as = "DisplayFromStr")]
-    whip: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    hitsPer9Inn: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    homeRunsPer9: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    walksPer9Inn: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    strikeoutsPer9Inn: f64,
-    #[serde_as(as = "DisplayFromStr")]
-    strikeoutWalkRatio: f64,
+    pub wins: u64,
+    pub losses: u64,
+    // Left out of situational splits
+    #[serde(default)]
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub win_percentage: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub era: Option<f64>,
+    pub games_pitched: u64,
+    pub games_started: u64,
+    pub games_finished: u64,
+    pub complete_games: u64,
+    pub shutouts: u64,
+    pub holds: u64,
+    pub saves: u64,
+    #[serde_as(deserialize_as = "DisplayFromStr")]
+    pub innings_pitched: f64,
+    pub hits: u64,
+    pub runs: u64,
+    pub earned_runs: u64,
+    pub home_runs: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub hit_by_pitch: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub whip: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub hits_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub home_runs_per9: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub walks_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeout_walk_ratio: Option<f64>,
+}
+
+// Any player's hitting and/or pitching lines, a group is None if it wasn't requested or the player has no
+// stats in it (e.g. a pitcher's hitting line after the universal DH). `hitting` and `pitching` are the season
+// totals, and the `_by_team` lines have one line per team the player was on
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+    pub person: Person,
+    pub hitting: Option<Batter>,
+    pub pitching: Option<Pitcher>,
+    pub hitting_by_team: Vec<SeasonLine<Batter>>,
+    pub pitching_by_team: Vec<SeasonLine<Pitcher>>,
+}
+
+pub(crate) fn stat_group_param(groups: &[StatGroup]) -> String {
+    groups.iter().map(StatGroup::as_str).collect::<Vec<&str>>().join(",")
+}
+
+// A player who changed teams mid-season has a split for each team, plus one with no team (and `numTeams`) for
+// the whole season. Returns the season total and the lines for each team
+fn season_splits<T>(splits: &[Value]) -> Result<(Option<T>, Vec<SeasonLine<T>>), MlbError> where T: DeserializeOwned {
+    let mut total = None;
+    let mut teams = Vec::new();
+    for split in splits {
+        let line: SeasonLine<T> = SeasonLine::deserialize(split)?;
+        match line.team {
+            Some(_) => teams.push(line),
+            None => total = Some(line.stat),
+        }
+    }
+
+    match (total, teams.len()) {
+        (Some(total), _) => Ok((Some(total), teams)),
+        (None, 0) => Ok((None, teams)),
+        (None, 1) => Ok((Some(T::deserialize(&splits[0]["stat"])?), teams)),
+        (None, _) => Err(MlbError::MissingField("season total split".to_string())),
+    }
+}
+
+fn group_stat<T>(person: &Value, group: StatGroup) -> Result<(Option<T>, Vec<SeasonLine<T>>), MlbError> where T: DeserializeOwned {
+    let splits = person["stats"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|stats| stats["group"]["displayName"] == group.as_str())
+        .and_then(|stats| stats["splits"].as_array());
+
+    match splits {
+        Some(splits) => season_splits(splits),
+        None => Ok((None, Vec::new())),
+    }
+}
+
+// Reads every stat group included in a player details response
+pub(crate) fn from_player_stats(details: Value) -> Result<PlayerStats, MlbError> {
+    let person = as_array(&details, "people")?
+        .first()
+        .ok_or_else(|| MlbError::MissingField("people".to_string()))?;
+
+    let (hitting, hitting_by_team) = group_stat(person, StatGroup::Hitting)?;
+    let (pitching, pitching_by_team) = group_stat(person, StatGroup::Pitching)?;
+    Ok(PlayerStats { person: Person::deserialize(person)?, hitting, pitching, hitting_by_team, pitching_by_team })
+}
+
+impl PlayerStats {
+    pub fn new(api: &StatsApi, player_id: u64, groups: &[StatGroup], season: &str) -> Result<Self, MlbError> {
+        from_player_stats(api.get_player_details(player_id, &stat_group_param(groups), season)?)
+    }
+}
+
+// Pulls the season total statline out of a player details response, None if the player has no stats in the season
+pub(crate) fn from_player_details<T>(player: Value) -> Result<Option<T>, MlbError> where T: DeserializeOwned {
+    let splits = player["people"][0]["stats"][0]["splits"].as_array().map(Vec::as_slice).unwrap_or_default();
+    Ok(season_splits(splits)?.0)
 }
 
 impl Batter {
-    pub fn new(player_id: u64, season: &str) -> Self {
-        let mut player = get_player_details(player_id, "hitting", season);
-        let stats = player["people"][0]["stats"][0]["splits"][0]["stat"].take();
-        serde_json::from_value(stats).unwrap()
+    pub fn new(api: &StatsApi, player_id: u64, season: &str) -> Result<Self, MlbError> {
+        match from_player_details(api.get_player_details(player_id, "hitting", season)?)? {
+            Some(batter) => Ok(batter),
+            None => Err(api.get_player_career(player_id, &[StatGroup::Hitting], false)?.no_stats(season)),
+        }
     }
 }
 
 impl Pitcher {
-    pub fn new(player_id: u64, season: &str) -> Self {
-        let mut player = get_player_details(player_id, "pitching", season);
-        let stats = player["people"][0]["stats"][0]["splits"][0]["stat"].take();
-        serde_json::from_value(stats).unwrap()
+    pub fn new(api: &StatsApi, player_id: u64, season: &str) -> Result<Self, MlbError> {
+        match from_player_details(api.get_player_details(player_id, "pitching", season)?)? {
+            Some(pitcher) => Ok(pitcher),
+            None => Err(api.get_player_career(player_id, &[StatGroup::Pitching], false)?.no_stats(season)),
+        }
     }
 }
 
 impl Player for Batter {
-    fn print_statline(&self) {
-        println!("{:.3}/{:.3}/{:.3}", self.avg, self.obp, self.slg);
+    fn stat_rows(&self) -> Result<Vec<StatRow>, MlbError> {
+        stat_rows(self, hitting::STATLINE)
+    }
+
+    fn print_statline(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        // Shown the way the statsapi does when there's no rate, e.g. no at bats
+        let rate = |rate: Option<f64>| rate.map(|rate| format!("{:.3}", rate)).unwrap_or_else(|| ".---".to_string());
+        writeln!(writer, "{}/{}/{}", rate(self.avg), rate(self.obp), rate(self.slg))
     }
 }
 
 impl Player for Pitcher {
-    fn print_statline(&self) {
-        println!("{} W-{} L, {} ERA", self.wins, self.losses, self.era);
+    fn stat_rows(&self) -> Result<Vec<StatRow>, MlbError> {
+        stat_rows(self, pitching::STATLINE)
+    }
+
+    fn print_statline(&self, writer: &mut dyn Write) -> std::io::Result<()> {
+        let era = pitching::EARNED_RUN_AVERAGE.format(self.era);
+        writeln!(writer, "{} W-{} L, {} ERA", self.wins, self.losses, era)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    // A full statline like the statsapi's, with `stat` filled in over it
+    fn full_stat(full: Value, stat: Value) -> Value {
+        let mut full = full;
+        full.as_object_mut().unwrap().extend(stat.as_object().unwrap().clone());
+        full
+    }
+
+    fn batter(stat: Value) -> Value {
+        full_stat(
+            json!({
+                "gamesPlayed": 1, "plateAppearances": 4, "atBats": 4, "runs": 0, "hits": 1, "doubles": 0, "triples": 0,
+                "homeRuns": 0, "rbi": 0, "stolenBases": 0, "caughtStealing": 0, "baseOnBalls": 0, "hitByPitch": 0,
+                "strikeOuts": 1, "avg": ".250", "babip": ".333", "obp": ".250", "slg": ".250", "ops": ".500"
+            }),
+            stat,
+        )
+    }
+
+    fn pitcher(stat: Value) -> Value {
+        full_stat(
+            json!({
+                "wins": 0, "losses": 0, "winPercentage": ".---", "era": "0.00", "gamesPitched": 1, "gamesStarted": 0,
+                "gamesFinished": 1, "completeGames": 0, "shutouts": 0, "holds": 0, "saves": 0, "inningsPitched": "1.0",
+                "hits": 0, "runs": 0, "earnedRuns": 0, "homeRuns": 0, "baseOnBalls": 0, "strikeOuts": 1, "hitByPitch": 0,
+                "whip": "0.00", "hitsPer9Inn": "0.00", "homeRunsPer9": "0.00", "walksPer9Inn": "0.00",
+                "strikeoutsPer9Inn": "9.00", "strikeoutWalkRatio": "-.--"
+            }),
+            stat,
+        )
+    }
+
+    #[test]
+    fn test_placeholder_rates() {
+        // A position player's mop-up inning with no decision, and no outs recorded
+        let pitcher: Pitcher = serde_json::from_value(pitcher(json!({
+            "era": "-.--",
+            "inningsPitched": "0.0",
+            "hits": 3,
+            "earnedRuns": 2,
+            "strikeOuts": 0,
+            "whip": "-.--"
+        })))
+        .unwrap();
+        assert_eq!((None, None, None), (pitcher.win_percentage, pitcher.era, pitcher.whip));
+        assert_eq!((1, 3, 0), (pitcher.games_pitched, pitcher.hits, pitcher.strike_outs));
+
+        let mut statline = Vec::new();
+        pitcher.print_statline(&mut statline).unwrap();
+        assert_eq!("0 W-0 L, -.-- ERA\n", String::from_utf8(statline).unwrap());
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        // Situational splits leave out the win percentage, anything else missing is an unexpected response
+        let mut split = pitcher(json!({}));
+        split.as_object_mut().unwrap().remove("winPercentage");
+        assert_eq!(None, serde_json::from_value::<Pitcher>(split).unwrap().win_percentage);
+
+        let mut stat = batter(json!({}));
+        stat.as_object_mut().unwrap().remove("atBats");
+        assert!(serde_json::from_value::<Batter>(stat).is_err());
+    }
+
+    #[test]
+    fn test_stat_rows() {
+        let stat = batter(json!({"atBats": 0, "hits": 0, "avg": ".---", "obp": "1.000", "slg": ".250"}));
+        let batter: Batter = serde_json::from_value(stat).unwrap();
+        let rows = batter.stat_rows().unwrap();
+        assert_eq!(19, rows.len());
+        let value = |label: &str| rows.iter().find(|row| row.label == label).unwrap().display_value();
+        assert_eq!(("1", ".---", "1.000", ".250"), (value("G").as_str(), value("BA").as_str(), value("OBP").as_str(), value("SLG").as_str()));
+
+        let mut statline = Vec::new();
+        batter.print_full_statline(&mut statline, StatlineMode::Compact).unwrap();
+        assert!(String::from_utf8(statline).unwrap().starts_with("G 1 | PA 4 | AB 0 |"));
+    }
+
+    #[test]
+    fn test_season_total() {
+        let split = |team: Option<u64>, hits: u64| match team {
+            Some(id) => json!({"season": "2022", "team": {"id": id, "name": "Team"}, "stat": batter(json!({"hits": hits}))}),
+            None => json!({"season": "2022", "numTeams": 2, "stat": batter(json!({"hits": hits}))}),
+        };
+
+        let (total, teams) = season_splits::<Batter>(&[split(None, 9), split(Some(147), 5), split(Some(119), 4)]).unwrap();
+        assert_eq!(Some(9), total.map(|batter| batter.hits));
+        assert_eq!(vec![147, 119], teams.iter().map(|line| line.team.as_ref().unwrap().id).collect::<Vec<u64>>());
+
+        let (total, teams) = season_splits::<Batter>(&[split(Some(137), 5)]).unwrap();
+        assert_eq!((Some(5), 1), (total.map(|batter| batter.hits), teams.len()));
+        assert!(season_splits::<Batter>(&[]).unwrap().0.is_none());
+    }
+}
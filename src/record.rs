@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use reqwest::Url;
+use serde_json::{json, Value};
+
+use crate::error::MlbError;
+use crate::transport::Transport;
+
+// File names are derived from the request so they're readable, with a hash of the full request so requests that
+// only differ in characters a file name can't have (e.g. `&` and `_`) don't collide. Very long requests (e.g.
+// hydrate queries) are truncated
+const MAX_FILE_STEM_LEN: usize = 150;
+
+// Identifies a request independently of the host it was sent to, so fixtures recorded against the live
+// statsapi can be replayed no matter what base URL the client is configured with
+pub(crate) fn request_key(url: &Url) -> String {
+    let query: Vec<String> = url
+        .query_pairs()
+        .map(|(key, value)| format!("{}={}", key, value))
+        .collect();
+
+    if query.is_empty() {
+        url.path().to_string()
+    } else {
+        format!("{}?{}", url.path(), query.join("&"))
+    }
+}
+
+// FNV-1a, used instead of std's hasher since file names need to be stable across Rust versions
+fn stable_hash(key: &str) -> u64 {
+    key.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub(crate) fn fixture_path(dir: &Path, url: &Url) -> PathBuf {
+    let key = request_key(url);
+    let mut stem: String = key
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || "-_.=,".contains(c) { c } else { '_' })
+        .collect::<String>()
+        .trim_matches('_')
+        .to_string();
+
+    stem.truncate(MAX_FILE_STEM_LEN);
+    stem.push_str(&format!("_{:016x}", stable_hash(&key)));
+
+    dir.join(format!("{}.json", stem))
+}
+
+pub(crate) fn write_fixture(path: &Path, url: &Url, response: &Value) -> Result<(), MlbError> {
+    let fixture = json!({
+        "request": request_key(url),
+        "response": response,
+    });
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&fixture)?)?;
+    Ok(())
+}
+
+pub(crate) fn read_fixture(path: &Path, url: &Url) -> Result<Option<Value>, MlbError> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut fixture: Value = serde_json::from_str(&contents)?;
+
+    // Guard against two requests hashing to the same file name
+    if fixture["request"].as_str() != Some(request_key(url).as_str()) {
+        return Ok(None);
+    }
+    Ok(Some(fixture["response"].take()))
+}
+
+// Saves every response from the wrapped transport to a fixture file in `dir`
+pub struct RecordingTransport<T> {
+    inner: T,
+    dir: PathBuf,
+}
+
+impl<T> RecordingTransport<T> where T: Transport {
+    pub fn new(inner: T, dir: &Path) -> Self {
+        RecordingTransport { inner, dir: dir.to_path_buf() }
+    }
+}
+
+impl<T> Transport for RecordingTransport<T> where T: Transport {
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        let response = self.inner.get(url)?;
+        write_fixture(&fixture_path(&self.dir, url), url, &response)?;
+        Ok(response)
+    }
+}
+
+// Serves responses saved by RecordingTransport without touching the network
+pub struct ReplayTransport {
+    dir: PathBuf,
+}
+
+impl ReplayTransport {
+    pub fn new(dir: &Path) -> Self {
+        ReplayTransport { dir: dir.to_path_buf() }
+    }
+}
+
+impl Transport for ReplayTransport {
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        let path = fixture_path(&self.dir, url);
+        match read_fixture(&path, url)? {
+            Some(response) => Ok(response),
+            None => Err(MlbError::InvalidInput(format!(
+                "No recorded response for {} in {}",
+                request_key(url),
+                self.dir.display()
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+    use reqwest::Url;
+
+    use super::{fixture_path, request_key};
+
+    #[test]
+    fn test_request_key_ignores_host() {
+        let live = Url::parse("https://statsapi.mlb.com/api/v1/teams?sportId=1&season=2022").unwrap();
+        let local = Url::parse("http://localhost:8080/api/v1/teams?sportId=1&season=2022").unwrap();
+
+        assert_eq!("/api/v1/teams?sportId=1&season=2022", request_key(&live));
+        assert_eq!(request_key(&live), request_key(&local));
+    }
+
+    #[test]
+    fn test_fixture_path_truncates_long_requests() {
+        let hydrate = "stats(group=[hitting],type=season,season=2022),currentTeam".repeat(5);
+        let url = Url::parse_with_params("https://statsapi.mlb.com/api/v1/people", &[("hydrate", hydrate)]).unwrap();
+        let path = fixture_path(Path::new("fixtures"), &url);
+        let file_name = path.file_name().unwrap().to_str().unwrap();
+
+        assert!(file_name.starts_with("api_v1_people_hydrate=stats_group=_hitting_"));
+        assert!(file_name.len() < 200);
+    }
+
+    #[test]
+    fn test_fixture_path_keeps_similar_requests_apart() {
+        let dir = Path::new("fixtures");
+        let query = Url::parse("https://statsapi.mlb.com/api/v1/stats?season=2023&group=hitting").unwrap();
+        let underscore = Url::parse("https://statsapi.mlb.com/api/v1/stats?season=2023_group=hitting").unwrap();
+        assert_ne!(fixture_path(dir, &query), fixture_path(dir, &underscore));
+
+        let slash = Url::parse("https://statsapi.mlb.com/api/v1/teams/137/roster").unwrap();
+        let joined = Url::parse("https://statsapi.mlb.com/api/v1/teams_137/roster").unwrap();
+        assert_ne!(fixture_path(dir, &slash), fixture_path(dir, &joined));
+    }
+}
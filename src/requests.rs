@@ -1,130 +1,339 @@
-use std::collections::HashMap;
+use chrono::NaiveDate;
 use reqwest::Url;
 use serde_json::Value;
 
-const MLB_LOOKUP_API_ENDPOINT: &str = "https://statsapi.mlb.com/api/v1";
+use crate::boxscore::{from_box_score, BoxScore};
+use crate::career::{from_player_career, PlayerCareer};
+use crate::error::MlbError;
+use crate::gamelog::{from_game_log, GameLog};
+use crate::splits::{from_player_splits, PlayerSplits};
+use crate::roster::{from_roster, RosterEntry, RosterType};
+use crate::standings::{from_standings, DivisionStandings};
+use crate::player::{stat_group_param, StatGroup};
+use crate::live::{from_live_feed, LiveFeed};
+use crate::meta::{from_stats_meta, StatsMeta};
+use crate::schedule::{from_schedule, ScheduleDate, ScheduleQuery};
+use crate::leaders::{from_league_leaders, from_team_leaders, LeaderBoard};
+use crate::team::{from_team_stats, from_teams, Team, TeamStats};
+use crate::transport::Transport;
+
+pub const MLB_LOOKUP_API_ENDPOINT: &str = "https://statsapi.mlb.com/api/v1";
 
 const PLAYER_LOOKUP: &str = "people";
+const PLAYER_SEARCH: &str = "people/search";
 const TEAMS_LOOKUP: &str = "teams";
 const SEARCH_PLAYER_ALL: &str = "sports/1/players";
 const STAT_LEADERS: &str = "stats/leaders";
+const GAME: &str = "game";
+const SCHEDULE: &str = "schedule";
+const STANDINGS: &str = "standings";
+const LEADER_TYPES: &str = "leagueLeaderTypes";
+const STAT_GROUPS: &str = "statGroups";
+const BASEBALL_STATS: &str = "baseballStats";
+// Lists that describe the rest of the statsapi rather than any season, see `StatsMeta`
+pub(crate) const META_ENDPOINTS: &[&str] = &[LEADER_TYPES, STAT_GROUPS, BASEBALL_STATS];
 
-fn get_formatted_url(path: &str) -> Result<Url, Box<dyn std::error::Error>> {
-    Ok(Url::parse(
-        format!("{}/{}", MLB_LOOKUP_API_ENDPOINT, path).as_str(),
-    )?)
+// A statsapi request independent of how it gets sent, shared by the blocking and async clients.
+// Query params are a list rather than a map so the generated URL is always the same for the same request
+pub(crate) struct Request {
+    path: String,
+    query_params: Vec<(&'static str, String)>,
 }
 
-fn get(path: &str, query_params: HashMap<&str, &str>) -> Result<Value, Box<dyn std::error::Error>> {
-    let mut url = get_formatted_url(path)?;
+impl Request {
+    fn new(path: &str, query_params: &[(&'static str, &str)]) -> Self {
+        Request {
+            path: path.to_string(),
+            query_params: query_params.iter().map(|&(key, value)| (key, value.to_string())).collect(),
+        }
+    }
+
+    pub(crate) fn url(&self, base_url: &str) -> Result<Url, MlbError> {
+        let mut url = Url::parse(format!("{}/{}", base_url, self.path).as_str())
+            .map_err(|e| MlbError::InvalidInput(format!("Invalid statsapi URL: {}", e)))?;
 
-    // Add query params, drop mutable ref to iterator once done
-    {
-        let mut url_query_params = url.query_pairs_mut();
+        // Add query params, drop mutable ref to iterator once done
+        {
+            let mut url_query_params = url.query_pairs_mut();
 
-        for (key, value) in query_params {
-            url_query_params.append_pair(key, value);
+            for (key, value) in &self.query_params {
+                url_query_params.append_pair(key, value);
+            }
         }
+
+        Ok(url)
     }
 
-    Ok(reqwest::blocking::get(url.as_str())?.json::<Value>()?)
-}
+    pub(crate) fn teams(season: &str) -> Self {
+        Request::new(
+            TEAMS_LOOKUP,
+            &[
+                ("sportId", "1"),
+                ("fields", "teams,id,name,abbreviation"),
+                ("season", season),
+            ],
+        )
+    }
 
-pub fn get_teams(season: &str) -> Value {
-    let resp = get(
-        TEAMS_LOOKUP,
-        HashMap::from([
-            ("sportId", "1"),
-            ("fields", "teams,id,abbreviation"),
-            ("season", season),
-        ]),
-    );
+    pub(crate) fn players(season: &str) -> Self {
+        Request::new(SEARCH_PLAYER_ALL, &[("season", season)])
+    }
 
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    // Every player the statsapi knows by the name, including ones who aren't on a season's list
+    pub(crate) fn search_people(name: &str) -> Self {
+        Request::new(PLAYER_SEARCH, &[("names", name), ("sportIds", "1")])
     }
-}
 
-pub fn get_players(season: &str) -> Value {
-    let resp = get(
-        SEARCH_PLAYER_ALL,
-        HashMap::from([("season", season)]),
-    );
+    pub(crate) fn player_details(player_id: u64, player_type: &str, season: &str) -> Self {
+        Request::new(
+            PLAYER_LOOKUP,
+            &[
+                ("personIds", player_id.to_string().as_str()),
+                (
+                    "hydrate",
+                    format!(
+                        "stats(group=[{}],type=season,season={}),currentTeam",
+                        player_type, season
+                    )
+                    .as_str(),
+                ),
+            ],
+        )
+    }
 
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    // Every season the player has played plus their career totals, `advanced` adds the yearByYearAdvanced lines
+    pub(crate) fn player_career(player_id: u64, player_type: &str, advanced: bool) -> Self {
+        let stat_types = if advanced { "yearByYear,yearByYearAdvanced,career" } else { "yearByYear,career" };
+        Request::new(
+            PLAYER_LOOKUP,
+            &[
+                ("personIds", player_id.to_string().as_str()),
+                ("hydrate", format!("stats(group=[{}],type=[{}]),currentTeam", player_type, stat_types).as_str()),
+            ],
+        )
     }
-}
 
-pub fn get_player_details(player_id: u64, player_type: &str, season: &str) -> Value {
-    let resp = get(
-        PLAYER_LOOKUP,
-        HashMap::from([
-            ("personIds", player_id.to_string().as_str()),
-            (
-                "hydrate",
-                format!(
-                    "stats(group=[{}],type=season,season={}),currentTeam",
-                    player_type, season
-                )
-                .as_str(),
-            ),
-        ]),
-    );
-
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    pub(crate) fn game_log(player_id: u64, player_type: &str, season: &str) -> Self {
+        Request::new(
+            PLAYER_LOOKUP,
+            &[
+                ("personIds", player_id.to_string().as_str()),
+                ("hydrate", format!("stats(group=[{}],type=[gameLog],season={}),currentTeam", player_type, season).as_str()),
+            ],
+        )
     }
-}
 
-pub fn get_team_stats(team_id: u64, season: &str) -> Value {
-    let path = format!("{}/{}/stats", TEAMS_LOOKUP, team_id);
-    let resp = get(
-        path.as_str(),
-        HashMap::from([
-            ("season", season),
-            ("group", "hitting,pitching"),
-            ("stats", "season"),
-        ]),
-    );
+    pub(crate) fn player_splits(player_id: u64, player_type: &str, sit_codes: &[String], season: &str) -> Self {
+        Request::new(
+            PLAYER_LOOKUP,
+            &[
+                ("personIds", player_id.to_string().as_str()),
+                (
+                    "hydrate",
+                    format!(
+                        "stats(group=[{}],type=[statSplits],sitCodes=[{}],season={}),currentTeam",
+                        player_type,
+                        sit_codes.join(","),
+                        season
+                    )
+                    .as_str(),
+                ),
+            ],
+        )
+    }
 
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    pub(crate) fn team_stats(team_id: u64, season: &str) -> Self {
+        Request::new(
+            format!("{}/{}/stats", TEAMS_LOOKUP, team_id).as_str(),
+            &[
+                ("season", season),
+                ("group", "hitting,pitching"),
+                ("stats", "season"),
+            ],
+        )
+    }
+
+    pub(crate) fn stat_leaders(leader_categories: &str, stat_type: &str, season: &str) -> Self {
+        Request::new(
+            STAT_LEADERS,
+            &[
+                ("season", season),
+                ("statGroup", stat_type),
+                ("leaderCategories", leader_categories),
+            ],
+        )
+    }
+
+    pub(crate) fn team_stat_leaders(team_id: u64, leader_categories: &str, season: &str) -> Self {
+        Request::new(
+            format!("{}/{}/leaders", TEAMS_LOOKUP, team_id).as_str(),
+            &[
+                ("season", season),
+                ("leaderCategories", leader_categories),
+            ],
+        )
+    }
+
+    pub(crate) fn box_score(game_pk: u64) -> Self {
+        Request::new(format!("{}/{}/boxscore", GAME, game_pk).as_str(), &[])
+    }
+
+    pub(crate) fn schedule(query: &ScheduleQuery) -> Self {
+        let mut query_params: Vec<(&'static str, String)> = vec![("sportId", "1".to_string())];
+        if let Some(date) = query.date {
+            query_params.push(("date", date.to_string()));
+        }
+        if let Some(start_date) = query.start_date {
+            query_params.push(("startDate", start_date.to_string()));
+        }
+        if let Some(end_date) = query.end_date {
+            query_params.push(("endDate", end_date.to_string()));
+        }
+        if let Some(team_id) = query.team_id {
+            query_params.push(("teamId", team_id.to_string()));
+        }
+        if let Some(game_type) = &query.game_type {
+            query_params.push(("gameType", game_type.to_string()));
+        }
+        query_params.push(("hydrate", "probablePitcher,linescore".to_string()));
+
+        Request { path: SCHEDULE.to_string(), query_params }
+    }
+
+    pub(crate) fn live_feed(game_pk: u64) -> Self {
+        Request::new(format!("{}/{}/feed/live", GAME, game_pk).as_str(), &[])
     }
-}
 
-pub fn get_stat_leaders(leader_categories: &str, stat_type: &str, season: &str) -> Value {
-    let resp = get(
-        STAT_LEADERS,
-        HashMap::from([
+    pub(crate) fn standings(league_ids: &[u64], season: &str, date: Option<NaiveDate>) -> Self {
+        let league_ids = league_ids.iter().map(u64::to_string).collect::<Vec<String>>().join(",");
+        let date = date.map(|date| date.to_string());
+
+        let mut query_params = vec![
+            ("leagueId", league_ids.as_str()),
             ("season", season),
-            ("statGroup", stat_type),
-            ("leaderCategories", leader_categories),
-        ]),
-    );
+            ("standingsTypes", "regularSeason"),
+        ];
+        if let Some(date) = &date {
+            query_params.push(("date", date));
+        }
+        query_params.push(("hydrate", "division"));
+        Request::new(STANDINGS, &query_params)
+    }
+
+    pub(crate) fn roster(team_id: u64, roster_type: RosterType, season: &str, date: Option<NaiveDate>) -> Self {
+        let date = date.map(|date| date.to_string());
+
+        let mut query_params = vec![("rosterType", roster_type.as_str()), ("season", season)];
+        if let Some(date) = &date {
+            query_params.push(("date", date));
+        }
+        query_params.push(("hydrate", "person"));
+        Request::new(format!("{}/{}/roster", TEAMS_LOOKUP, team_id).as_str(), &query_params)
+    }
+
+    pub(crate) fn linescore(game_pk: u64) -> Self {
+        Request::new(format!("{}/{}/linescore", GAME, game_pk).as_str(), &[])
+    }
+
+    pub(crate) fn leader_types() -> Self {
+        Request::new(LEADER_TYPES, &[])
+    }
+
+    pub(crate) fn stat_groups() -> Self {
+        Request::new(STAT_GROUPS, &[])
+    }
 
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    pub(crate) fn baseball_stats() -> Self {
+        Request::new(BASEBALL_STATS, &[])
     }
 }
 
-pub fn get_team_stat_leaders(team_id: u64, leader_categories: &str, season: &str) -> Value {
-    let path = format!("{}/{}/leaders", TEAMS_LOOKUP, team_id);
-    let resp = get(
-        path.as_str(),
-        HashMap::from([
-            ("season", season),
-            ("leaderCategories", leader_categories),
-        ]),
-    );
+pub(crate) fn validate_base_url(base_url: &str) -> Result<String, MlbError> {
+    let base_url = base_url.trim_end_matches('/').to_string();
+    Url::parse(&base_url).map_err(|e| MlbError::InvalidInput(format!("Invalid base URL {}: {}", base_url, e)))?;
+    Ok(base_url)
+}
+
+pub struct StatsApi {
+    base_url: String,
+    transport: Box<dyn Transport>,
+}
+
+impl StatsApi {
+    pub fn new(base_url: &str, transport: Box<dyn Transport>) -> Result<Self, MlbError> {
+        Ok(StatsApi { base_url: validate_base_url(base_url)?, transport })
+    }
+
+    fn fetch(&self, request: Request) -> Result<Value, MlbError> {
+        self.transport.get(&request.url(&self.base_url)?)
+    }
+
+    pub fn get_teams(&self, season: &str) -> Result<Vec<Team>, MlbError> {
+        from_teams(self.fetch(Request::teams(season))?)
+    }
+
+    pub fn get_players(&self, season: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::players(season))
+    }
+
+    pub fn search_people(&self, name: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::search_people(name))
+    }
+
+    pub fn get_player_details(&self, player_id: u64, player_type: &str, season: &str) -> Result<Value, MlbError> {
+        self.fetch(Request::player_details(player_id, player_type, season))
+    }
+
+    pub fn get_player_career(&self, player_id: u64, groups: &[StatGroup], advanced: bool) -> Result<PlayerCareer, MlbError> {
+        from_player_career(self.fetch(Request::player_career(player_id, &stat_group_param(groups), advanced))?)
+    }
 
-    match resp {
-        Ok(response) => response,
-        Err(e) => panic!("Failed to get response: {}", e),
+    pub fn get_game_log(&self, player_id: u64, groups: &[StatGroup], season: &str) -> Result<GameLog, MlbError> {
+        from_game_log(self.fetch(Request::game_log(player_id, &stat_group_param(groups), season))?)
     }
-}
\ No newline at end of file
+
+    // `sit_codes` are situation codes like vl, see `check_sit_codes`
+    pub fn get_player_splits(&self, player_id: u64, groups: &[StatGroup], sit_codes: &[String], season: &str) -> Result<PlayerSplits, MlbError> {
+        let request = Request::player_splits(player_id, &stat_group_param(groups), sit_codes, season);
+        from_player_splits(self.fetch(request)?, sit_codes)
+    }
+
+    pub fn get_team_stats(&self, team_id: u64, season: &str) -> Result<TeamStats, MlbError> {
+        from_team_stats(self.fetch(Request::team_stats(team_id, season))?)
+    }
+
+    pub fn get_stat_leaders(&self, leader_categories: &str, stat_type: &str, season: &str) -> Result<Vec<LeaderBoard>, MlbError> {
+        from_league_leaders(self.fetch(Request::stat_leaders(leader_categories, stat_type, season))?)
+    }
+
+    pub fn get_team_stat_leaders(&self, team_id: u64, leader_categories: &str, season: &str) -> Result<Vec<LeaderBoard>, MlbError> {
+        from_team_leaders(self.fetch(Request::team_stat_leaders(team_id, leader_categories, season))?)
+    }
+
+    // Every leader category, stat group and stat the statsapi has
+    pub fn get_stats_meta(&self) -> Result<StatsMeta, MlbError> {
+        let leader_types = self.fetch(Request::leader_types())?;
+        from_stats_meta(leader_types, self.fetch(Request::stat_groups())?, self.fetch(Request::baseball_stats())?)
+    }
+
+    pub fn get_box_score(&self, game_pk: u64) -> Result<BoxScore, MlbError> {
+        let box_score = self.fetch(Request::box_score(game_pk))?;
+        from_box_score(game_pk, box_score, self.fetch(Request::linescore(game_pk))?)
+    }
+
+    pub fn get_schedule(&self, query: &ScheduleQuery) -> Result<Vec<ScheduleDate>, MlbError> {
+        from_schedule(self.fetch(Request::schedule(query))?)
+    }
+
+    pub fn get_live_feed(&self, game_pk: u64) -> Result<LiveFeed, MlbError> {
+        from_live_feed(self.fetch(Request::live_feed(game_pk))?)
+    }
+
+    pub fn get_standings(&self, league_ids: &[u64], season: &str, date: Option<NaiveDate>) -> Result<Vec<DivisionStandings>, MlbError> {
+        from_standings(self.fetch(Request::standings(league_ids, season, date))?)
+    }
+
+    pub fn get_roster(&self, team_id: u64, roster_type: RosterType, season: &str, date: Option<NaiveDate>) -> Result<Vec<RosterEntry>, MlbError> {
+        from_roster(self.fetch(Request::roster(team_id, roster_type, season, date))?)
+    }
+}
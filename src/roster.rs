@@ -0,0 +1,135 @@
+use std::fmt;
+use std::str::FromStr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::search::normalize_name;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RosterType {
+    #[default]
+    Active,
+    FortyMan,
+    FullSeason,
+    DepthChart,
+}
+
+impl RosterType {
+    // Name the API expects for the rosterType param
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RosterType::Active => "active",
+            RosterType::FortyMan => "40Man",
+            RosterType::FullSeason => "fullSeason",
+            RosterType::DepthChart => "depthChart",
+        }
+    }
+}
+
+impl FromStr for RosterType {
+    type Err = MlbError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "active" => Ok(RosterType::Active),
+            "40man" => Ok(RosterType::FortyMan),
+            "fullseason" => Ok(RosterType::FullSeason),
+            "depthchart" => Ok(RosterType::DepthChart),
+            other => Err(MlbError::InvalidInput(format!(
+                "Roster type must be one of active, 40Man, fullSeason or depthChart, got \"{}\"",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for RosterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Hand {
+    pub code: String,
+    pub description: String,
+}
+
+// The person as hydrated on the roster, which adds the handedness a plain `Person` doesn't have
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterPerson {
+    pub id: u64,
+    pub full_name: String,
+    pub bat_side: Option<Hand>,
+    pub pitch_hand: Option<Hand>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Position {
+    pub code: String,
+    pub name: String,
+    pub abbreviation: String,
+}
+
+// e.g. A (Active), D60 (Injured 60-Day) or MIN (Reassigned to Minors)
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct RosterStatus {
+    pub code: String,
+    pub description: String,
+}
+
+// A depth chart lists a player once per position they're on the chart for
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterEntry {
+    pub person: RosterPerson,
+    pub jersey_number: Option<String>,
+    pub position: Position,
+    pub status: RosterStatus,
+}
+
+impl RosterEntry {
+    // e.g. "L/R" for a player who bats left and throws right
+    pub fn bats_throws(&self) -> String {
+        let code = |hand: &Option<Hand>| hand.as_ref().map(|hand| hand.code.to_string()).unwrap_or_else(|| "-".to_string());
+        format!("{}/{}", code(&self.person.bat_side), code(&self.person.pitch_hand))
+    }
+}
+
+#[derive(Deserialize)]
+struct RosterResponse {
+    #[serde(default)]
+    roster: Vec<RosterEntry>,
+}
+
+pub(crate) fn from_roster(resp: Value) -> Result<Vec<RosterEntry>, MlbError> {
+    Ok(serde_json::from_value::<RosterResponse>(resp)?.roster)
+}
+
+// Finds a player on the roster by jersey number, or else by (part of) their name
+pub fn find_roster_player<'a>(roster: &'a [RosterEntry], query: &str) -> Result<&'a RosterEntry, MlbError> {
+    let query = query.trim();
+    if let Some(entry) = roster.iter().find(|entry| entry.jersey_number.as_deref() == Some(query)) {
+        return Ok(entry);
+    }
+
+    // Depth charts list a player once per position, so only count each player once
+    let mut matches: Vec<&RosterEntry> = Vec::new();
+    for entry in roster {
+        let is_match = normalize_name(&entry.person.full_name).contains(&normalize_name(query));
+        if is_match && !matches.iter().any(|other| other.person.id == entry.person.id) {
+            matches.push(entry);
+        }
+    }
+    match matches.as_slice() {
+        [] => Err(MlbError::InvalidInput(format!("No player on the roster matching \"{}\"", query))),
+        [entry] => Ok(entry),
+        _ => Err(MlbError::InvalidInput(format!(
+            "\"{}\" matches more than one player on the roster: {}",
+            query,
+            matches.iter().map(|entry| entry.person.full_name.as_str()).collect::<Vec<&str>>().join(", ")
+        ))),
+    }
+}
@@ -0,0 +1,108 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::MlbError;
+use crate::player::Person;
+use crate::team::Team;
+
+// Filters for the schedule endpoint. Either a single date or a date range, optionally narrowed to one team
+// and a game type (R regular season, P postseason, S spring training, ...)
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleQuery {
+    pub date: Option<NaiveDate>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub team_id: Option<u64>,
+    pub game_type: Option<String>,
+}
+
+impl ScheduleQuery {
+    pub fn date(date: NaiveDate) -> Self {
+        ScheduleQuery { date: Some(date), ..Default::default() }
+    }
+
+    pub fn range(start_date: NaiveDate, end_date: NaiveDate) -> Self {
+        ScheduleQuery { start_date: Some(start_date), end_date: Some(end_date), ..Default::default() }
+    }
+
+    pub fn team(mut self, team_id: u64) -> Self {
+        self.team_id = Some(team_id);
+        self
+    }
+
+    pub fn game_type(mut self, game_type: &str) -> Self {
+        self.game_type = Some(game_type.to_string());
+        self
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStatus {
+    pub abstract_game_state: String,
+    pub detailed_state: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleTeam {
+    pub team: Team,
+    pub score: Option<u64>,
+    pub is_winner: Option<bool>,
+    pub probable_pitcher: Option<Person>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ScheduleTeams {
+    pub away: ScheduleTeam,
+    pub home: ScheduleTeam,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameInning {
+    pub current_inning: Option<u64>,
+    pub current_inning_ordinal: Option<String>,
+    pub inning_state: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledGame {
+    pub game_pk: u64,
+    pub game_date: String,
+    pub game_type: String,
+    pub status: GameStatus,
+    pub teams: ScheduleTeams,
+    pub linescore: Option<GameInning>,
+}
+
+impl ScheduledGame {
+    // e.g. "Top 5th" for a game in progress, or "10th" for a final that went to extras
+    pub fn inning(&self) -> Option<String> {
+        let linescore = self.linescore.as_ref()?;
+        let ordinal = linescore.current_inning_ordinal.as_deref()?;
+        match self.status.abstract_game_state.as_str() {
+            "Live" => Some(format!("{} {}", linescore.inning_state.as_deref().unwrap_or_default(), ordinal)),
+            "Final" if linescore.current_inning.is_some_and(|inning| inning != 9) => Some(ordinal.to_string()),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ScheduleDate {
+    pub date: String,
+    pub games: Vec<ScheduledGame>,
+}
+
+#[derive(Deserialize)]
+struct ScheduleResponse {
+    #[serde(default)]
+    dates: Vec<ScheduleDate>,
+}
+
+pub(crate) fn from_schedule(resp: Value) -> Result<Vec<ScheduleDate>, MlbError> {
+    Ok(serde_json::from_value::<ScheduleResponse>(resp)?.dates)
+}
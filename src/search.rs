@@ -0,0 +1,188 @@
+use serde_json::Value;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// Scores for how a query matches a name, best first. Typos only count when nothing matches outright
+const EXACT_SCORE: f64 = 1.0;
+const PREFIX_SCORE: f64 = 0.9;
+const WORD_PREFIX_SCORE: f64 = 0.8;
+const CONTAINS_SCORE: f64 = 0.7;
+const FUZZY_SCORE: f64 = 0.6;
+// How close each query word has to be to a word in the name to count as a typo of it
+const MIN_WORD_SIMILARITY: f64 = 0.7;
+
+// Lowercases a name and strips its accents and punctuation, so "Ronald Acuña Jr." becomes "ronald acuna jr"
+pub fn normalize_name(name: &str) -> String {
+    name.nfd()
+        .filter(|&c| !is_combining_mark(c))
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+// Optimal string alignment distance, i.e. edits counting a swap of two neighbouring letters as one
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in rows[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+    rows[a.len()][b.len()]
+}
+
+// 1.0 for the same word, down to 0.0 for nothing in common
+fn word_similarity(a: &str, b: &str) -> f64 {
+    let (a, b): (Vec<char>, Vec<char>) = (a.chars().collect(), b.chars().collect());
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / longest as f64
+}
+
+// How well a normalized query matches one normalized name, 0.0 if it doesn't
+fn name_score(query: &str, name: &str) -> f64 {
+    if query.is_empty() || name.is_empty() {
+        return 0.0;
+    }
+    if name == query {
+        return EXACT_SCORE;
+    }
+    if name.starts_with(query) {
+        return PREFIX_SCORE;
+    }
+
+    let name_words: Vec<&str> = name.split(' ').collect();
+    let query_words: Vec<&str> = query.split(' ').collect();
+    if query_words.iter().all(|word| name_words.iter().any(|name_word| name_word.starts_with(word))) {
+        return WORD_PREFIX_SCORE;
+    }
+    if name.contains(query) {
+        return CONTAINS_SCORE;
+    }
+
+    let similarities: Vec<f64> = query_words
+        .iter()
+        .map(|word| name_words.iter().map(|name_word| word_similarity(word, name_word)).fold(0.0, f64::max))
+        .collect();
+    if similarities.iter().all(|&similarity| similarity >= MIN_WORD_SIMILARITY) {
+        FUZZY_SCORE * similarities.iter().sum::<f64>() / similarities.len() as f64
+    } else {
+        0.0
+    }
+}
+
+// Best score over every name the statsapi has for a player: their full name, the name they go by
+// (e.g. "Mike Trout" for Michael Trout) and "last, first"
+pub fn match_score(query: &str, player: &Value) -> f64 {
+    let query = normalize_name(query);
+    let use_name = match (player["useName"].as_str(), player["lastName"].as_str()) {
+        (Some(use_name), Some(last_name)) => Some(format!("{} {}", use_name, last_name)),
+        _ => None,
+    };
+
+    ["fullName", "nameFirstLast", "lastFirstName"]
+        .iter()
+        .filter_map(|field| player[field].as_str().map(str::to_string))
+        .chain(use_name)
+        .map(|name| name_score(&query, &normalize_name(&name)))
+        .fold(0.0, f64::max)
+}
+
+fn scored_players<'a>(query: &str, players: &'a [Value]) -> Vec<(f64, &'a Value)> {
+    let mut scored: Vec<(f64, &Value)> = players.iter().map(|player| (match_score(query, player), player)).collect();
+    // Stable, so players with the same score stay in list order
+    scored.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    scored
+}
+
+// Players matching the query, best match first. Typos are only matched when nothing matches outright, so
+// "Smith" lists every Smith but "Ohtnai" still finds Ohtani
+pub fn search_players<'a>(query: &str, players: &'a [Value]) -> Vec<&'a Value> {
+    let mut matches = scored_players(query, players);
+    let min_score = match matches.first() {
+        Some(&(score, _)) if score >= CONTAINS_SCORE => CONTAINS_SCORE,
+        _ => f64::MIN_POSITIVE,
+    };
+    matches.retain(|&(score, _)| score >= min_score);
+    matches.into_iter().map(|(_, player)| player).collect()
+}
+
+// Every player, best match first. For results the statsapi already matched, like `people/search`
+pub fn rank_players<'a>(query: &str, players: &'a [Value]) -> Vec<&'a Value> {
+    scored_players(query, players).into_iter().map(|(_, player)| player).collect()
+}
+
+// The single player the query picks out, when exactly one of them has the name exactly
+pub fn exact_match<'a>(query: &str, players: &[&'a Value]) -> Option<&'a Value> {
+    let mut exact = players.iter().filter(|player| match_score(query, player) == EXACT_SCORE);
+    match (exact.next(), exact.next()) {
+        (Some(player), None) => Some(*player),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    fn players() -> Vec<Value> {
+        vec![
+            json!({"fullName": "Willie Smithson", "useName": "Willie", "lastName": "Smithson", "lastFirstName": "Smithson, Willie"}),
+            json!({"fullName": "Ronald Acuña Jr.", "useName": "Ronald", "lastName": "Acuña", "lastFirstName": "Acuña Jr., Ronald"}),
+            json!({"fullName": "Michael Trout", "useName": "Mike", "lastName": "Trout", "lastFirstName": "Trout, Michael"}),
+            json!({"fullName": "Shohei Ohtani", "useName": "Shohei", "lastName": "Ohtani", "lastFirstName": "Ohtani, Shohei"}),
+            json!({"fullName": "Will Smith", "useName": "Will", "lastName": "Smith", "lastFirstName": "Smith, Will"}),
+            json!({"fullName": "Dominic Smith", "useName": "Dominic", "lastName": "Smith", "lastFirstName": "Smith, Dominic"}),
+        ]
+    }
+
+    fn names(players: &[&Value]) -> Vec<String> {
+        players.iter().map(|player| player["fullName"].as_str().unwrap().to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!("ronald acuna jr", normalize_name("Ronald Acuña Jr."));
+        assert_eq!("jose ramirez", normalize_name("  José   Ramírez "));
+        assert_eq!("ke bryan hayes", normalize_name("Ke'Bryan Hayes"));
+    }
+
+    #[test]
+    fn test_search_players() {
+        let players = players();
+        assert_eq!(vec!["Ronald Acuña Jr."], names(&search_players("acuna", &players)));
+        assert_eq!(vec!["Michael Trout"], names(&search_players("Mike Trout", &players)));
+        assert_eq!(vec!["Shohei Ohtani"], names(&search_players("Ohtnai", &players)));
+        assert_eq!(vec!["Willie Smithson", "Will Smith", "Dominic Smith"], names(&search_players("smith", &players)));
+        assert_eq!(vec!["Will Smith", "Willie Smithson"], names(&search_players("Will Smith", &players)));
+        assert_eq!(vec!["Dominic Smith"], names(&search_players("Smith, Dom", &players)));
+        assert!(search_players("Judge", &players).is_empty());
+    }
+
+    #[test]
+    fn test_exact_match() {
+        let players = players();
+        let matches = search_players("will smith", &players);
+        assert_eq!(Some("Will Smith"), exact_match("will smith", &matches).and_then(|player| player["fullName"].as_str()));
+        assert_eq!(None, exact_match("smith", &search_players("smith", &players)));
+    }
+}
@@ -0,0 +1,97 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::{as_array, MlbError};
+use crate::player::{Batter, Person, Pitcher, StatGroup};
+
+// Situation codes the statsapi splits on, with the names that can be used for a whole set of them
+pub const HOME_AWAY: &[&str] = &["h", "a"];
+pub const PLATOON: &[&str] = &["vl", "vr"];
+pub const RISP: &[&str] = &["risp"];
+pub const DAY_NIGHT: &[&str] = &["d", "n"];
+pub const MONTHS: &[&str] = &["3", "4", "5", "6", "7", "8", "9", "10"];
+pub const DEFAULT_SIT_CODES: &[&str] = &["h", "a", "vl", "vr", "risp", "d", "n"];
+
+// Expands names like "platoon" into their situation codes, and checks that everything else is a known code
+pub fn check_sit_codes<S>(sit_codes: &[S]) -> Result<Vec<String>, MlbError> where S: AsRef<str> {
+    let mut codes: Vec<String> = Vec::new();
+    for sit_code in sit_codes {
+        let sit_code = sit_code.as_ref().trim().to_lowercase();
+        let expanded: Vec<&str> = match sit_code.as_str() {
+            "home-away" => HOME_AWAY.to_vec(),
+            "platoon" => PLATOON.to_vec(),
+            "day-night" => DAY_NIGHT.to_vec(),
+            "months" => MONTHS.to_vec(),
+            code if [HOME_AWAY, PLATOON, RISP, DAY_NIGHT, MONTHS].concat().contains(&code) => vec![code],
+            other => {
+                return Err(MlbError::InvalidInput(format!(
+                    "Unknown situation \"{}\", expected home-away, platoon, risp, day-night, months or a code like vl",
+                    other
+                )))
+            }
+        };
+        for code in expanded {
+            if !codes.iter().any(|existing| existing == code) {
+                codes.push(code.to_string());
+            }
+        }
+    }
+    Ok(codes)
+}
+
+// e.g. code "vl" with description "vs Left"
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Situation {
+    pub code: String,
+    pub description: String,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct SituationLine<T> {
+    #[serde(rename(deserialize = "split"))]
+    pub situation: Situation,
+    pub stat: T,
+}
+
+// Lines are in the order the situations were asked for
+#[derive(Serialize, Debug)]
+pub struct PlayerSplits {
+    pub person: Person,
+    pub hitting: Vec<SituationLine<Batter>>,
+    pub pitching: Vec<SituationLine<Pitcher>>,
+}
+
+fn group_splits<T>(person: &Value, group: StatGroup, sit_codes: &[String]) -> Result<Vec<SituationLine<T>>, MlbError>
+where
+    T: DeserializeOwned,
+{
+    let splits = person["stats"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|stats| stats["type"]["displayName"] == "statSplits" && stats["group"]["displayName"] == group.as_str())
+        .and_then(|stats| stats["splits"].as_array());
+
+    let mut lines = Vec::new();
+    for split in splits.into_iter().flatten() {
+        let line: SituationLine<T> = SituationLine::deserialize(split)?;
+        if let Some(position) = sit_codes.iter().position(|code| *code == line.situation.code) {
+            lines.push((position, line));
+        }
+    }
+    lines.sort_by_key(|(position, _)| *position);
+    Ok(lines.into_iter().map(|(_, line)| line).collect())
+}
+
+pub(crate) fn from_player_splits(details: Value, sit_codes: &[String]) -> Result<PlayerSplits, MlbError> {
+    let person = as_array(&details, "people")?
+        .first()
+        .ok_or_else(|| MlbError::MissingField("people".to_string()))?;
+
+    Ok(PlayerSplits {
+        person: Person::deserialize(person)?,
+        hitting: group_splits(person, StatGroup::Hitting, sit_codes)?,
+        pitching: group_splits(person, StatGroup::Pitching, sit_codes)?,
+    })
+}
@@ -0,0 +1,232 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::error::MlbError;
+use crate::team::Team;
+
+pub const AMERICAN_LEAGUE_ID: u64 = 103;
+pub const NATIONAL_LEAGUE_ID: u64 = 104;
+
+pub fn league_name(league_id: u64) -> &'static str {
+    match league_id {
+        AMERICAN_LEAGUE_ID => "American League",
+        NATIONAL_LEAGUE_ID => "National League",
+        _ => "Unknown League",
+    }
+}
+
+// Division names are only included when hydrated, these cover the current alignment otherwise
+fn default_division_name(division_id: u64) -> &'static str {
+    match division_id {
+        200 => "American League West",
+        201 => "American League East",
+        202 => "American League Central",
+        203 => "National League West",
+        204 => "National League East",
+        205 => "National League Central",
+        _ => "Unknown Division",
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct Division {
+    pub id: u64,
+    pub name: Option<String>,
+}
+
+impl Division {
+    pub fn display_name(&self) -> &str {
+        self.name.as_deref().unwrap_or_else(|| default_division_name(self.id))
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct WinLoss {
+    pub wins: u64,
+    pub losses: u64,
+}
+
+impl std::fmt::Display for WinLoss {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.wins, self.losses)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStanding {
+    pub team: Team,
+    pub wins: u64,
+    pub losses: u64,
+    pub winning_percentage: f64,
+    pub games_back: String,
+    pub wild_card_games_back: Option<String>,
+    pub division_rank: Option<u64>,
+    pub wild_card_rank: Option<u64>,
+    pub division_leader: bool,
+    pub streak: Option<String>,
+    pub last_ten: Option<WinLoss>,
+    pub home: Option<WinLoss>,
+    pub away: Option<WinLoss>,
+    pub runs_scored: u64,
+    pub runs_allowed: u64,
+    pub run_differential: i64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DivisionStandings {
+    pub league_id: u64,
+    pub division: Division,
+    pub teams: Vec<TeamStanding>,
+}
+
+// Everyone in a league chasing a wild card spot, i.e. every team that isn't leading its division
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WildCardStandings {
+    pub league_id: u64,
+    pub teams: Vec<TeamStanding>,
+}
+
+// Ranks are strings in the response, e.g. "divisionRank": "1"
+fn rank<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error> where D: Deserializer<'de> {
+    let rank: Option<String> = Option::deserialize(deserializer)?;
+    Ok(rank.and_then(|rank| rank.parse().ok()))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SplitRecord {
+    #[serde(rename = "type")]
+    kind: String,
+    wins: u64,
+    losses: u64,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct RecordSplits {
+    split_records: Vec<SplitRecord>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "camelCase")]
+struct Streak {
+    streak_code: Option<String>,
+}
+
+#[serde_as]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTeamRecord {
+    team: Team,
+    wins: u64,
+    losses: u64,
+    #[serde_as(deserialize_as = "DisplayFromStr")]
+    winning_percentage: f64,
+    games_back: String,
+    wild_card_games_back: Option<String>,
+    #[serde(default, deserialize_with = "rank")]
+    division_rank: Option<u64>,
+    #[serde(default, deserialize_with = "rank")]
+    wild_card_rank: Option<u64>,
+    #[serde(default)]
+    division_leader: bool,
+    #[serde(default)]
+    streak: Streak,
+    #[serde(default)]
+    records: RecordSplits,
+    #[serde(default)]
+    runs_scored: u64,
+    #[serde(default)]
+    runs_allowed: u64,
+    #[serde(default)]
+    run_differential: i64,
+}
+
+#[derive(Deserialize)]
+struct LeagueRef {
+    id: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawDivisionRecord {
+    league: LeagueRef,
+    division: Division,
+    team_records: Vec<RawTeamRecord>,
+}
+
+#[derive(Deserialize)]
+struct StandingsResponse {
+    #[serde(default)]
+    records: Vec<RawDivisionRecord>,
+}
+
+impl From<RawTeamRecord> for TeamStanding {
+    fn from(record: RawTeamRecord) -> Self {
+        let split = |kind: &str| {
+            record
+                .records
+                .split_records
+                .iter()
+                .find(|split| split.kind == kind)
+                .map(|split| WinLoss { wins: split.wins, losses: split.losses })
+        };
+
+        TeamStanding {
+            last_ten: split("lastTen"),
+            home: split("home"),
+            away: split("away"),
+            team: record.team,
+            wins: record.wins,
+            losses: record.losses,
+            winning_percentage: record.winning_percentage,
+            games_back: record.games_back,
+            wild_card_games_back: record.wild_card_games_back,
+            division_rank: record.division_rank,
+            wild_card_rank: record.wild_card_rank,
+            division_leader: record.division_leader,
+            streak: record.streak.streak_code,
+            runs_scored: record.runs_scored,
+            runs_allowed: record.runs_allowed,
+            run_differential: record.run_differential,
+        }
+    }
+}
+
+pub(crate) fn from_standings(resp: Value) -> Result<Vec<DivisionStandings>, MlbError> {
+    Ok(serde_json::from_value::<StandingsResponse>(resp)?
+        .records
+        .into_iter()
+        .map(|record| DivisionStandings {
+            league_id: record.league.id,
+            division: record.division,
+            teams: record.team_records.into_iter().map(TeamStanding::from).collect(),
+        })
+        .collect())
+}
+
+// Builds the wild card race for each league in `standings`, in wild card order
+pub fn wild_card_standings(standings: &[DivisionStandings]) -> Vec<WildCardStandings> {
+    let mut leagues: Vec<WildCardStandings> = Vec::new();
+    for division in standings {
+        let contenders = division.teams.iter().filter(|team| !team.division_leader).cloned();
+        match leagues.iter_mut().find(|league| league.league_id == division.league_id) {
+            Some(league) => league.teams.extend(contenders),
+            None => leagues.push(WildCardStandings { league_id: division.league_id, teams: contenders.collect() }),
+        }
+    }
+
+    for league in &mut leagues {
+        league.teams.sort_by(|a, b| {
+            a.wild_card_rank
+                .unwrap_or(u64::MAX)
+                .cmp(&b.wild_card_rank.unwrap_or(u64::MAX))
+                .then(b.winning_percentage.total_cmp(&a.winning_percentage))
+        });
+    }
+    leagues
+}
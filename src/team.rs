@@ -0,0 +1,142 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use serde_with::{serde_as, DefaultOnError, DisplayFromStr};
+
+use crate::error::MlbError;
+
+// Abbreviation is only included by the teams endpoint, other responses just reference a team by id and name
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Team {
+    pub id: u64,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abbreviation: Option<String>,
+}
+
+// Rates are None where the statsapi has a placeholder instead of a number, e.g. ".---" before a team's first game
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamHittingStats {
+    pub games_played: u64,
+    pub plate_appearances: u64,
+    pub at_bats: u64,
+    pub runs: u64,
+    pub hits: u64,
+    pub doubles: u64,
+    pub triples: u64,
+    pub home_runs: u64,
+    pub rbi: u64,
+    pub stolen_bases: u64,
+    pub caught_stealing: u64,
+    pub base_on_balls: u64,
+    pub hit_by_pitch: u64,
+    pub intentional_walks: u64,
+    pub strike_outs: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub avg: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub obp: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub slg: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub ops: Option<f64>,
+}
+
+#[serde_as]
+#[derive(Deserialize, Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamPitchingStats {
+    pub games_played: u64,
+    pub wins: u64,
+    pub losses: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub win_percentage: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub era: Option<f64>,
+    pub complete_games: u64,
+    pub shutouts: u64,
+    pub holds: u64,
+    pub saves: u64,
+    #[serde_as(deserialize_as = "DisplayFromStr")]
+    pub innings_pitched: f64,
+    pub hits: u64,
+    pub runs: u64,
+    pub earned_runs: u64,
+    pub home_runs: u64,
+    pub base_on_balls: u64,
+    pub strike_outs: u64,
+    pub hit_by_pitch: u64,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub whip: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub hits_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub home_runs_per9: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub walks_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeouts_per9_inn: Option<f64>,
+    #[serde_as(deserialize_as = "DefaultOnError<Option<DisplayFromStr>>")]
+    pub strikeout_walk_ratio: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct TeamStats {
+    pub team: Team,
+    pub hitting: TeamHittingStats,
+    pub pitching: TeamPitchingStats,
+}
+
+#[derive(Deserialize)]
+struct TeamsResponse {
+    teams: Vec<Team>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupName {
+    display_name: String,
+}
+
+#[derive(Deserialize)]
+struct TeamStatSplit {
+    team: Team,
+    stat: Value,
+}
+
+#[derive(Deserialize)]
+struct TeamStatGroup {
+    group: GroupName,
+    splits: Vec<TeamStatSplit>,
+}
+
+#[derive(Deserialize)]
+struct TeamStatsResponse {
+    stats: Vec<TeamStatGroup>,
+}
+
+pub(crate) fn from_teams(resp: Value) -> Result<Vec<Team>, MlbError> {
+    Ok(serde_json::from_value::<TeamsResponse>(resp)?.teams)
+}
+
+// Team stats come back as one entry per stat group, in no particular order
+pub(crate) fn from_team_stats(resp: Value) -> Result<TeamStats, MlbError> {
+    let mut groups = serde_json::from_value::<TeamStatsResponse>(resp)?.stats;
+    let mut take_split = |group: &str| -> Result<TeamStatSplit, MlbError> {
+        groups
+            .iter_mut()
+            .find(|stat_group| stat_group.group.display_name == group)
+            .and_then(|stat_group| stat_group.splits.drain(..).next())
+            .ok_or_else(|| MlbError::MissingField(format!("{} splits", group)))
+    };
+
+    let hitting = take_split("hitting")?;
+    let pitching = take_split("pitching")?;
+    Ok(TeamStats {
+        team: hitting.team,
+        hitting: serde_json::from_value(hitting.stat)?,
+        pitching: serde_json::from_value(pitching.stat)?,
+    })
+}
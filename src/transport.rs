@@ -0,0 +1,58 @@
+use std::time::Duration;
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::error::MlbError;
+
+// Anything that can turn a statsapi URL into a JSON response. The default is HttpTransport, but tests and
+// CI can swap in a closure or fixture-backed implementation that never touches the network.
+pub trait Transport {
+    fn get(&self, url: &Url) -> Result<Value, MlbError>;
+}
+
+impl<F> Transport for F
+where
+    F: Fn(&Url) -> Result<Value, MlbError>
+{
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        self(url)
+    }
+}
+
+impl Transport for Box<dyn Transport> {
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        self.as_ref().get(url)
+    }
+}
+
+pub struct HttpTransport {
+    client: reqwest::blocking::Client,
+}
+
+impl HttpTransport {
+    pub fn new(timeout: Option<Duration>, connect_timeout: Option<Duration>, user_agent: &str) -> Result<Self, MlbError> {
+        let mut builder = reqwest::blocking::Client::builder()
+            .user_agent(user_agent)
+            .timeout(timeout);
+
+        if let Some(connect_timeout) = connect_timeout {
+            builder = builder.connect_timeout(connect_timeout);
+        }
+
+        Ok(HttpTransport { client: builder.build()? })
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get(&self, url: &Url) -> Result<Value, MlbError> {
+        let resp = self.client.get(url.as_str()).send()?;
+        if !resp.status().is_success() {
+            return Err(MlbError::Status {
+                url: url.to_string(),
+                status: resp.status().as_u16(),
+            });
+        }
+
+        Ok(serde_json::from_str(&resp.text()?)?)
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "async")]
+
+use mlb::{AsyncMlbClient, AsyncTransport, BoxFuture, MlbClientBuilder, MlbError, Url};
+use serde_json::Value;
+
+mod common;
+use common::fixture_transport;
+
+struct FixtureTransport;
+
+impl AsyncTransport for FixtureTransport {
+    fn get<'a>(&'a self, url: &'a Url) -> BoxFuture<'a, Result<Value, MlbError>> {
+        Box::pin(async move {
+            // Yield once so joined requests actually interleave
+            tokio::task::yield_now().await;
+            fixture_transport(url)
+        })
+    }
+}
+
+fn create_client() -> AsyncMlbClient {
+    AsyncMlbClient::new(mlb::MLB_LOOKUP_API_ENDPOINT, Box::new(FixtureTransport)).unwrap()
+}
+
+#[tokio::test]
+async fn test_get_batter_and_pitcher() -> Result<(), MlbError> {
+    let (batter, pitcher) = create_client().get_batter_and_pitcher(660271, "2022").await?;
+
+    assert_eq!(34, batter.home_runs);
+    assert_eq!(219, pitcher.strike_outs);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_concurrent_requests() -> Result<(), MlbError> {
+    let client = create_client();
+    let (teams, leaders) = tokio::join!(
+        client.get_teams("2022"),
+        client.get_stat_leaders("homeRuns", "hitting", "2022")
+    );
+
+    assert_eq!(5, teams?.len());
+    assert_eq!("Aaron Judge", leaders?[0].leaders[0].person.full_name);
+    Ok(())
+}
+
+#[test]
+fn test_build_async_rejects_caching() {
+    let result = MlbClientBuilder::new()
+        .cache_dir(Some(std::path::Path::new("cache")))
+        .build_async();
+
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+}
@@ -0,0 +1,76 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+use mlb::{MlbError, Url};
+use serde_json::Value;
+
+fn query(url: &Url, key: &str) -> String {
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+        .unwrap_or_default()
+}
+
+// Picks the stat group out of a hydrate like `stats(group=[hitting],type=season,season=2022)`
+fn hydrated_group(url: &Url) -> String {
+    let hydrate = query(url, "hydrate");
+    hydrate
+        .split_once("group=[")
+        .and_then(|(_, rest)| rest.split_once(']'))
+        .map(|(group, _)| group.replace(',', "_"))
+        .unwrap_or_default()
+}
+
+// Stat types other than the default season, e.g. `type=[yearByYear,career]` becomes `_yearByYear_career`
+fn hydrated_types(url: &Url) -> String {
+    let hydrate = query(url, "hydrate");
+    hydrate
+        .split_once("type=[")
+        .and_then(|(_, rest)| rest.split_once(']'))
+        .map(|(types, _)| format!("_{}", types.replace(',', "_")))
+        .unwrap_or_default()
+}
+
+// The season a stat hydrate asks for, e.g. `season=2022` becomes `_2022`. Career stats have none
+fn hydrated_season(url: &Url) -> String {
+    let hydrate = query(url, "hydrate");
+    hydrate
+        .split_once("season=")
+        .map(|(_, rest)| format!("_{}", rest.split(|c: char| !c.is_ascii_digit()).next().unwrap_or_default()))
+        .unwrap_or_default()
+}
+
+// Serves canned statsapi responses from tests/fixtures so the tests don't depend on the live API
+pub fn fixture_transport(url: &Url) -> Result<Value, MlbError> {
+    let path = url.path().trim_start_matches("/api/v1/");
+    let fixture = match path {
+        "teams" => "teams".to_string(),
+        "leagueLeaderTypes" | "statGroups" | "baseballStats" => path.to_string(),
+        "sports/1/players" => "players".to_string(),
+        "people/search" => format!("people_search_{}", query(url, "names").to_lowercase().replace(' ', "_")),
+        "people" => {
+            format!("people_{}_{}{}{}", query(url, "personIds"), hydrated_group(url), hydrated_types(url), hydrated_season(url))
+        }
+        "standings" => format!("standings_{}", query(url, "season")),
+        "schedule" => format!("schedule_{}", query(url, "date")),
+        "stats/leaders" => format!("leaders_{}", query(url, "leaderCategories")),
+        path if path.ends_with("/roster") => {
+            format!("roster_{}_{}", path.trim_start_matches("teams/").trim_end_matches("/roster"), query(url, "rosterType"))
+        }
+        path if path.ends_with("/stats") => "team_stats".to_string(),
+        path if path.ends_with("/leaders") => format!("team_leaders_{}", query(url, "leaderCategories")),
+        path if path.starts_with("game/") => path.replace('/', "_"),
+        _ => return Err(MlbError::Status { url: url.to_string(), status: 404 }),
+    };
+
+    let contents = fs::read_to_string(format!("{}/tests/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), fixture))
+        .map_err(|_| MlbError::Status { url: url.to_string(), status: 404 })?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("mlb-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
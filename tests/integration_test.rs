@@ -1,51 +1,936 @@
-use mlb;
+use std::cell::Cell;
+use std::fs;
+use std::io::Cursor;
+use std::rc::Rc;
+use mlb::{
+    AdvancedBatter, AdvancedPitcher, Batter, MlbClient, MlbClientBuilder, MlbError, OutputFormat, Pitcher, RosterType, StatGroup, StatlineMode, StatsApi,
+    TeamHittingStats, TeamPitchingStats, Url, MLB_LOOKUP_API_ENDPOINT,
+};
+use serde_json::Value;
 
-// TODO: Capture output, it's complicated to do now
+mod common;
+use common::{fixture_transport, temp_dir};
+
+type TestClient = MlbClient<Cursor<String>, Vec<u8>>;
+
+fn create_client(input: &[&str]) -> Result<TestClient, MlbError> {
+    MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .build_with_output(Cursor::new(input.join("\n") + "\n"), Vec::new())
+}
+
+fn create_noninteractive_client() -> Result<TestClient, MlbError> {
+    // No input at all, so any prompt would fail
+    MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .season(Some("2022"))
+        .build_with_output(Cursor::new(String::new()), Vec::new())
+}
+
+fn create_formatted_client(format: OutputFormat) -> Result<TestClient, MlbError> {
+    MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .season(Some("2022"))
+        .format(format)
+        .build_with_output(Cursor::new(String::new()), Vec::new())
+}
+
+fn output(client: TestClient) -> String {
+    String::from_utf8(client.into_writer()).unwrap()
+}
 
 #[test]
-fn test_get_batter() {
-    let input = ["2022", "Joey Bart"].join("\n") + "\n";
-    mlb::create_client(input.as_bytes()).get_player();
+fn test_get_batter() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "Joey Bart"])?;
+    client.get_player()?;
+
+    assert!(output(client).ends_with("Printing statline for player...\n0.215/0.296/0.379\n"));
+    Ok(())
 }
 
 #[test]
-fn test_get_pitcher() {
-    let input = ["2022", "Logan Webb"].join("\n") + "\n";
-    mlb::create_client(input.as_bytes()).get_player();
+fn test_get_pitcher() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "Logan Webb"])?;
+    client.get_player()?;
+
+    assert!(output(client).contains("15 W-9 L, 2.90 ERA"));
+    Ok(())
+}
+
+#[test]
+fn test_get_multiple_players() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "Will Smith", "1"])?;
+    client.get_player()?;
+
+    let output = output(client);
+    assert!(output.contains("2 players found"));
+    assert!(output.contains("1) Will Smith, LAD (C)"));
+    assert!(output.contains("2) Will Smith, ATL (P)"));
+    assert!(output.contains("0.260/0.343/0.465"));
+    Ok(())
+}
+
+#[test]
+fn test_get_two_way_player() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_with(Some("Ohtani"))?;
+
+    assert_eq!("Printing statline for player...\n0.273/0.356/0.519\n15 W-9 L, 2.33 ERA\n", output(client));
+    Ok(())
+}
+
+#[test]
+fn test_get_player_stat_group() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_stats_with(Some("Ohtani"), Some(&[StatGroup::Hitting]))?;
+    client.get_player_stats_with(Some("Logan Webb"), Some(&[StatGroup::Hitting]))?;
+
+    let output = output(client);
+    assert!(output.contains("0.273/0.356/0.519\n"));
+    assert!(!output.contains("ERA"));
+    assert!(output.ends_with("No hitting stats for Logan Webb in 2022\n"));
+    Ok(())
+}
+
+#[test]
+fn test_get_missing_player() -> Result<(), MlbError> {
+    let result = create_client(&["2022", "Nobody Atall"])?.get_player();
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_player_no_stats() -> Result<(), MlbError> {
+    let result = create_noninteractive_client()?.get_player_with(Some("Buster Posey"));
+    let error = result.unwrap_err();
+    assert!(error.to_string().starts_with("No stats for Buster Posey in 2022. Seasons with stats: 2009, 2010, "));
+    match error {
+        MlbError::NoStats { seasons, .. } => {
+            assert_eq!(12, seasons.len());
+            assert_eq!(Some("2021"), seasons.last().map(String::as_str));
+            assert!(!seasons.contains(&"2020".to_string()));
+        }
+        other => panic!("expected NoStats, got {:?}", other),
+    }
+
+    let api = StatsApi::new(MLB_LOOKUP_API_ENDPOINT, Box::new(fixture_transport))?;
+    assert!(matches!(Batter::new(&api, 457763, "2022"), Err(MlbError::NoStats { .. })));
+    Ok(())
+}
+
+#[test]
+fn test_placeholder_rates() -> Result<(), MlbError> {
+    // Webb's 2020 hitting line has no at bats, so the statsapi has ".---" for each rate
+    let mut client = create_noninteractive_client()?;
+    client.get_player_career_with(Some("Logan Webb"), Some(&[StatGroup::Hitting, StatGroup::Pitching]), false)?;
+
+    let tables = output(client);
+    assert!(tables.contains("\n2020       | SF         | 1          | 1          | 0"));
+    assert!(tables.contains("\n2021       | SF         | 26         | 48         | 41"));
+    assert!(tables.contains("Logan Webb Pitching:"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_career_with(Some("Logan Webb"), Some(&[StatGroup::Hitting]), false)?;
+    let career: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!(Value::Null, career["hitting"]["seasons"][1]["stat"]["avg"]);
+    assert_eq!(0.122, career["hitting"]["seasons"][2]["stat"]["avg"]);
+    Ok(())
+}
+
+#[test]
+fn test_player_search() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_with(Some("acuna"))?;
+    client.get_player_with(Some("Ohtnai"))?;
+
+    let statlines = output(client);
+    assert!(statlines.contains("0.266/0.351/0.413\n"));
+    assert!(statlines.contains("0.273/0.356/0.519\n"));
+    Ok(())
+}
+
+#[test]
+fn test_player_search_fallback() -> Result<(), MlbError> {
+    // Ruth isn't on the season's list of players, so he's found through the statsapi's people search
+    let mut client = MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .season(Some("1927"))
+        .format(OutputFormat::Json)
+        .build_with_output(Cursor::new(String::new()), Vec::new())?;
+    client.get_player_with(Some("Babe Ruth"))?;
+
+    let stats: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("Babe Ruth", stats["person"]["fullName"]);
+    assert_eq!(60, stats["hitting"]["homeRuns"]);
+    Ok(())
+}
+
+#[test]
+fn test_full_statline() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_full_stats_with(Some("Joey Bart"), None, StatlineMode::Standard)?;
+    client.get_player_full_stats_with(Some("Logan Webb"), None, StatlineMode::Compact)?;
+
+    let statlines = output(client);
+    assert!(statlines.contains("Joey Bart Hitting:\nG        97  Games played\nPA      291  Plate appearances\n"));
+    assert!(statlines.contains("\nBAbip  .329  Batting average on balls in play\n"));
+    assert!(statlines.contains("Logan Webb Pitching:\nW 15 | L 9 | W-L% .625 | ERA 2.90 | G 32 |"));
+    assert!(statlines.contains("| IP 192.1 |"));
+    assert!(statlines.ends_with("| SO9 7.63 | SO/W 3.33\n"));
+
+    let mut client = create_formatted_client(OutputFormat::Csv)?;
+    client.get_player_full_stats_with(Some("Joey Bart"), None, StatlineMode::Standard)?;
+    let csv = output(client);
+    assert!(csv.starts_with("Stat,Value,Description\nG,97,Games played\n"));
+    assert!(csv.contains("\nOPS,.675,On-base plus slugging\n"));
+    Ok(())
 }
 
 #[test]
-fn test_get_multiple_players() {
-    let input = ["2022", "Will Smith", "1"].join("\n") + "\n";
-    mlb::create_client(input.as_bytes()).get_player();
+fn test_traded_player() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_with(Some("Joey Gallo"))?;
+    client.get_player_stats_by_team_with(Some("Joey Gallo"), None)?;
+
+    let statlines = output(client);
+    assert!(statlines.starts_with("Printing statline for player...\n0.160/0.278/0.351\n"));
+    assert!(statlines.contains("Joey Gallo Hitting by Team:\nTm         | G          | PA"));
+    assert!(statlines.contains("\nNYY        | 82         | 280"));
+    assert!(statlines.contains("\nLAD        | 44         | 130"));
+    assert!(statlines.contains("\n2TM        | 126        | 410"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_with(Some("Joey Gallo"))?;
+    let stats: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!(19, stats["hitting"]["homeRuns"]);
+    assert_eq!(2, stats["hittingByTeam"].as_array().unwrap().len());
+    assert_eq!(147, stats["hittingByTeam"][0]["team"]["id"]);
+
+    let api = StatsApi::new(MLB_LOOKUP_API_ENDPOINT, Box::new(fixture_transport))?;
+    assert_eq!(126, Batter::new(&api, 608336, "2022")?.games_played);
+    Ok(())
 }
 
 #[test]
-fn test_get_team_stats() {
-    let input = ["2022", "1"].join("\n") + "\n";
-    mlb::create_client(input.as_bytes()).get_team_stats();
+fn test_get_player_career() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_career_with(Some("Joey Bart"), None, false)?;
+
+    let output = output(client);
+    assert!(output.contains("Joey Bart Batting:\nYear       | Tm         | G          | PA         | AB"));
+    assert!(output.contains("\n2020       | SF         | 33         | 111        | 103"));
+    assert!(output.contains("\n2022       | SF         | 97         | 291        | 261"));
+    assert!(output.contains("\nCareer     |            | 132        | 408        | 369"));
+    Ok(())
 }
 
 #[test]
-fn test_get_hitting_stat_leaders() {
-    let input = ["2022", "hitting", "2"].join("\n") + "\n"; // HRs
-    mlb::create_client(input.as_bytes()).get_stat_leaders();
+fn test_get_player_career_advanced() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_career_with(Some("Logan Webb"), None, true)?;
+
+    let tables = output(client);
+    assert!(tables.contains("\n2019       | SF         | 2          | 3          | 5.22"));
+    assert!(tables.contains("\nCareer     |            | 31         | 19         | 3.48"));
+    assert!(tables.contains("Logan Webb Advanced Pitching:\nYear       | Tm         | BF         | QS"));
+    assert!(tables.contains("\n2022       | SF         | 791        | 23"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_career_with(Some("Logan Webb"), None, true)?;
+    let career: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!(4, career["pitching"]["seasons"].as_array().unwrap().len());
+    assert_eq!(31, career["pitching"]["total"]["wins"]);
+    assert_eq!(14, career["pitching"]["advanced"][2]["stat"]["qualityStarts"]);
+    assert_eq!(Value::Null, career["hitting"]);
+    Ok(())
 }
 
 #[test]
-fn test_get_pitching_stat_leaders() {
-    let input = ["2022", "pitching", "3"].join("\n") + "\n"; // ERA
-    mlb::create_client(input.as_bytes()).get_stat_leaders();
+fn test_advanced_placeholder_rates() -> Result<(), MlbError> {
+    // Webb's one 2020 plate appearance was a sacrifice, so his advanced rates are ".---" and "-.--"
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_career_with(Some("Logan Webb"), Some(&[StatGroup::Hitting]), true)?;
+
+    let career: Value = serde_json::from_str(&output(client)).unwrap();
+    let advanced = &career["hitting"]["advanced"];
+    assert_eq!(Value::Null, advanced[1]["stat"]["babip"]);
+    assert_eq!(Value::Null, advanced[1]["stat"]["walksPerStrikeout"]);
+    assert_eq!(0.238, advanced[2]["stat"]["babip"]);
+    Ok(())
+}
+
+// A fixture's first stat line with some of its rates swapped for the statsapi's placeholders
+fn placeholder_stat(fixture: &str, entry: usize, rates: &[&str]) -> Value {
+    let path = format!("{}/tests/fixtures/{}.json", env!("CARGO_MANIFEST_DIR"), fixture);
+    let fixture: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    let stats = fixture.get("people").map_or(&fixture, |people| &people[0]);
+    let mut stat = stats["stats"][entry]["splits"][0]["stat"].clone();
+    for (index, rate) in rates.iter().enumerate() {
+        stat[rate] = Value::from(if index % 2 == 0 { ".---" } else { "-.--" });
+    }
+    stat
 }
 
 #[test]
-fn test_get_hitting_team_stat_leaders() {
-    let input = ["2022", "hitting", "1", "2"].join("\n") + "\n"; // HRs
-    mlb::create_client(input.as_bytes()).get_team_stat_leaders();
+fn test_placeholder_rates_in_every_model() -> Result<(), MlbError> {
+    let batter: Batter = serde_json::from_value(placeholder_stat("people_663698_hitting_2022", 0, &["avg", "ops"]))?;
+    assert_eq!((None, None, Some(0.296)), (batter.avg, batter.ops, batter.obp));
+
+    let pitcher: Pitcher = serde_json::from_value(placeholder_stat("people_657277_pitching_2022", 0, &["whip", "era"]))?;
+    assert_eq!((None, None), (pitcher.whip, pitcher.era));
+
+    let advanced: AdvancedBatter =
+        serde_json::from_value(placeholder_stat("people_657277_hitting_yearByYear_yearByYearAdvanced_career", 1, &["iso", "walksPerStrikeout"]))?;
+    assert_eq!((None, None), (advanced.iso, advanced.walks_per_strikeout));
+
+    let advanced: AdvancedPitcher =
+        serde_json::from_value(placeholder_stat("people_657277_pitching_yearByYear_yearByYearAdvanced_career", 1, &["babip", "strikeoutsPer9"]))?;
+    assert_eq!((None, None), (advanced.babip, advanced.strikeouts_per9));
+
+    let hitting: TeamHittingStats = serde_json::from_value(placeholder_stat("team_stats", 0, &["avg", "ops"]))?;
+    assert_eq!((None, None, Some(0.315)), (hitting.avg, hitting.ops, hitting.obp));
+
+    let pitching: TeamPitchingStats = serde_json::from_value(placeholder_stat("team_stats", 1, &["winPercentage", "era"]))?;
+    assert_eq!((None, None), (pitching.win_percentage, pitching.era));
+    Ok(())
 }
 
 #[test]
-fn test_get_pitching_team_stat_leaders() {
-    let input = ["2022", "pitching", "1", "3"].join("\n") + "\n"; // ERA
-    mlb::create_client(input.as_bytes()).get_team_stat_leaders();
-}
\ No newline at end of file
+fn test_team_stats_placeholder_rates_are_dashes() -> Result<(), MlbError> {
+    let mut fixture = fixture_transport(&Url::parse("https://statsapi.mlb.com/api/v1/teams/137/stats").unwrap())?;
+    fixture["stats"][0]["splits"][0]["stat"]["avg"] = Value::from(".---");
+    let transport = move |url: &Url| match url.path().ends_with("/stats") {
+        true => Ok(fixture.clone()),
+        false => fixture_transport(url),
+    };
+    let mut client = MlbClientBuilder::new()
+        .transport(transport)
+        .season(Some("2022"))
+        .build_with_output(Cursor::new(String::new()), Vec::new())?;
+    client.get_team_stats_with(Some("SF"))?;
+
+    let output = output(client);
+    assert!(output.contains("| 1462       | -          | 0.315      | 0.39       | 0.705"));
+    Ok(())
+}
+
+#[test]
+fn test_game_log_last_games() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_game_log_with(Some("Joey Bart"), None, Some(3), None, None)?;
+
+    let games = output(client);
+    assert!(games.contains("Joey Bart Batting Game Log:\nDate       | Opp                  | Rslt       | PA         | AB"));
+    assert!(games.contains("\n2022-09-30 | LAD                  | W          | 3          | 3          | 0          | 1"));
+    assert!(games.contains("\n2022-10-02 | LAD                  | L          |"));
+    assert!(!games.contains("2022-09-27"));
+    Ok(())
+}
+
+#[test]
+fn test_game_log_date_range() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_game_log_with(Some("Logan Webb"), None, None, Some("2022-09-13"), Some("2022-09-25"))?;
+
+    let games = output(client);
+    assert!(games.contains("\n2022-09-13 | ATL               | L          | L          | 6.0"));
+    assert!(games.contains("\n2022-09-25 | @San Diego Padres | W          | W          | 6.1"));
+    assert!(!games.contains("2022-09-07") && !games.contains("2022-09-30"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_game_log_with(Some("Logan Webb"), None, Some(1), None, None)?;
+    let game_log: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("2022-09-30", game_log["pitching"][0]["date"]);
+    assert_eq!(662021, game_log["pitching"][0]["game"]["gamePk"]);
+    assert_eq!(1, game_log["pitching"].as_array().unwrap().len());
+
+    let result = create_noninteractive_client()?.get_game_log_with(Some("Logan Webb"), None, None, Some("9/13"), None);
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_player_splits() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_splits_with(Some("Joey Bart"), None, None)?;
+
+    let splits = output(client);
+    assert!(splits.contains("Joey Bart Batting Splits:\nSplit                       | G          | PA"));
+    assert!(splits.contains("\nHome Games                  | 50         | 150        | 134"));
+    assert!(splits.contains("\nvs Left                     | 60         | 85         | 75"));
+    assert!(splits.contains("\nRunners In Scoring Position |"));
+    assert!(!splits.contains("April"));
+
+    let mut client = create_noninteractive_client()?;
+    client.get_player_splits_with(Some("Joey Bart"), None, Some(&["months".to_string()]))?;
+    let splits = output(client);
+    assert!(splits.contains("\nApril      | 16         | 45"));
+    assert!(splits.contains("\nOctober    | 4          | 14"));
+    assert!(!splits.contains("Home Games"));
+    Ok(())
+}
+
+#[test]
+fn test_pitcher_splits() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_splits_with(Some("Logan Webb"), None, Some(&["platoon".to_string(), "h".to_string()]))?;
+
+    let splits = output(client);
+    assert!(splits.contains("Logan Webb Pitching Splits:\nSplit      | IP         | H "));
+    assert!(splits.contains("\nHome Games | 99.1       | 82         | 28         | 25"));
+    assert!(splits.find("vs Left") < splits.find("Home Games"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_splits_with(Some("Logan Webb"), None, Some(&["vr".to_string()]))?;
+    let splits: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("vr", splits["pitching"][0]["situation"]["code"]);
+    assert_eq!(93, splits["pitching"][0]["stat"]["strikeOuts"]);
+    assert_eq!(1, splits["pitching"].as_array().unwrap().len());
+
+    let result = create_noninteractive_client()?.get_player_splits_with(Some("Logan Webb"), None, Some(&["lefties".to_string()]));
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_compare_players() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.compare_players_with(&["Joey Bart".to_string(), "Shohei Ohtani 2021".to_string()])?;
+
+    let comparison = output(client);
+    assert!(comparison.contains("Hitting Comparison:\nStat       | Joey Bart (2022) | Shohei Ohtani (2021)"));
+    assert!(comparison.contains("\nHR         | 11               | 46*"));
+    assert!(comparison.contains("\nSO         | 112*             | 189"));
+    assert!(!comparison.contains("Pitching Comparison"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.compare_players_with(&["Joey Bart".to_string(), "Shohei Ohtani".to_string()])?;
+    let comparison: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("2022", comparison[0]["season"]);
+    assert_eq!("Shohei Ohtani", comparison[1]["person"]["fullName"]);
+    assert_eq!(34, comparison[1]["hitting"]["homeRuns"]);
+    assert_eq!(Value::Null, comparison[1]["pitching"]);
+    Ok(())
+}
+
+#[test]
+fn test_compare_players_invalid() -> Result<(), MlbError> {
+    let result = create_noninteractive_client()?.compare_players_with(&["Joey Bart".to_string(), "Logan Webb".to_string()]);
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+
+    let result = create_noninteractive_client()?.compare_players_with(&["Joey Bart".to_string()]);
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+
+    let result = create_noninteractive_client()?.compare_players_with(&["Joey Bart".to_string(), "Joey Bart".to_string()]);
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_get_team_stats() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "1"])?;
+    client.get_team_stats()?;
+
+    let output = output(client);
+    assert!(output.contains("San Francisco Giants Hitting:"));
+    assert!(output.contains("San Francisco Giants Pitching:"));
+    Ok(())
+}
+
+#[test]
+fn test_get_hitting_stat_leaders() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "hitting", "2"])?; // HRs
+    client.get_stat_leaders()?;
+
+    let output = output(client);
+    assert!(output.contains("Leaders in homeRuns:\n1) Aaron Judge (62)\n"));
+    // Tied leaders share a rank
+    assert!(output.contains("\n3) Vladimir Guerrero Jr. (40)\n3) Pete Alonso (40)\n5) Austin Riley (39)\n"));
+    assert!(!output.contains("Freddie Freeman"));
+    Ok(())
+}
+
+#[test]
+fn test_get_pitching_stat_leaders() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "pitching", "3"])?; // ERA
+    client.get_stat_leaders()?;
+
+    assert!(output(client).contains("Leaders in earnedRunAverage:\n1) Justin Verlander (1.75)\n"));
+    Ok(())
+}
+
+#[test]
+fn test_leader_categories_menu() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "pitching", "3"])?; // ERA
+    client.get_stat_leaders()?;
+
+    let output = output(client);
+    assert!(output.contains("Enter stat type for leaders (hitting, pitching, fielding, catching): "));
+    assert!(output.contains("3) ERA        Earned run average\n"));
+    assert!(output.contains(") GF         Games finished\n"));
+    assert!(output.contains(") P/IP       Pitches per inning\n"));
+    assert!(output.contains(") blownSaves Blown saves\n"));
+    assert!(!output.contains("putOuts"));
+    Ok(())
+}
+
+#[test]
+fn test_stat_leaders_beyond_registry() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_stat_leaders_with(Some("hitting"), Some("2B"), 2)?;
+
+    assert!(output(client).contains("Leaders in doubles:\n1) Freddie Freeman (47)\n2) José Ramírez (44)\n"));
+    Ok(())
+}
+
+#[test]
+fn test_stat_leaders_by_leader_name() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_stat_leaders_with(Some("hitting"), Some("homeRuns"), 1)?;
+
+    assert!(output(client).contains("Leaders in homeRuns:\n1) Aaron Judge (62)\n"));
+    Ok(())
+}
+
+#[test]
+fn test_get_hitting_team_stat_leaders() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "hitting", "1", "2"])?; // HRs
+    client.get_team_stat_leaders()?;
+
+    let output = output(client);
+    assert!(output.contains("1) Joc Pederson (20)"));
+    assert!(!output.contains("Alex Wood"));
+    Ok(())
+}
+
+#[test]
+fn test_get_pitching_team_stat_leaders() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "pitching", "1", "3"])?; // ERA
+    client.get_team_stat_leaders()?;
+
+    assert!(output(client).contains("2) Logan Webb (2.90)"));
+    Ok(())
+}
+
+#[test]
+fn test_get_player_by_name() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_player_with(Some("joey bart"))?;
+
+    // Nothing should have been prompted for
+    assert_eq!("Printing statline for player...\n0.215/0.296/0.379\n", output(client));
+    Ok(())
+}
+
+#[test]
+fn test_get_team_stats_by_abbreviation() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_team_stats_with(Some("sf"))?;
+
+    assert!(output(client).contains("716        | 1261"));
+    Ok(())
+}
+
+#[test]
+fn test_get_stat_leaders_with_flags() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_stat_leaders_with(Some("pitching"), Some("ERA"), 10)?;
+
+    assert!(output(client).contains("5) Shohei Ohtani (2.33)"));
+    Ok(())
+}
+
+#[test]
+fn test_get_team_stat_leaders_with_flags() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_team_stat_leaders_with(Some("SF"), Some("hitting"), Some("HR"), 3)?;
+
+    let output = output(client);
+    assert!(output.contains("3) Mike Yastrzemski (17)"));
+    assert!(!output.contains("LaMonte Wade Jr."));
+    Ok(())
+}
+
+#[test]
+fn test_unknown_flags() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+
+    assert!(matches!(client.get_team_stats_with(Some("XYZ")), Err(MlbError::InvalidInput(_))));
+    assert!(matches!(client.get_stat_leaders_with(Some("hitting"), Some("ERA"), 5), Err(MlbError::InvalidInput(_))));
+    assert!(matches!(client.get_stat_leaders_with(Some("fielding"), Some("E"), 5), Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_player_json() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_player_with(Some("Logan Webb"))?;
+
+    let player: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("Logan Webb", player["person"]["fullName"]);
+    assert_eq!(15, player["pitching"]["wins"]);
+    assert_eq!(2.9, player["pitching"]["era"]);
+    assert!(player["hitting"].is_null());
+    Ok(())
+}
+
+#[test]
+fn test_team_stats_json() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_team_stats_with(Some("SF"))?;
+
+    let stats: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("San Francisco Giants", stats["team"]["name"]);
+    assert_eq!(716, stats["hitting"]["runs"]);
+    assert_eq!(3.85, stats["pitching"]["era"]);
+    Ok(())
+}
+
+#[test]
+fn test_team_stats_csv() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Csv)?;
+    client.get_team_stats_with(Some("SF"))?;
+
+    let output = output(client);
+    assert!(output.starts_with("R,H,2B,3B,HR,RBI,SB,CS,BB,HBP,IBB,SO,BA,OBP,SLG,OPS\n716,1261,"));
+    assert!(output.contains("\n\nW,L,W-L%,ERA,"));
+    Ok(())
+}
+
+#[test]
+fn test_leaders_markdown() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Markdown)?;
+    client.get_stat_leaders_with(Some("hitting"), Some("HR"), 2)?;
+
+    assert_eq!(
+        "### Leaders in homeRuns\n\n| rank | name | team | value |\n|---|---|---|---|\n\
+         | 1 | Aaron Judge | New York Yankees | 62 |\n| 2 | Kyle Schwarber | Philadelphia Phillies | 46 |\n",
+        output(client)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_box_score() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_box_score_with(Some(662021))?;
+
+    let output = output(client);
+    assert!(output.starts_with("Los Angeles Dodgers 3, San Francisco Giants 5\n"));
+    assert!(output.contains("LAD         0  0  1  0  0  2  0  0  0    3  7  1\n"));
+    assert!(output.contains("SF          1  0  0  3  0  0  1  0  x    5  9  0\n"));
+    assert!(output.contains("  Hanser Alberto PH"));
+    assert!(output.contains("Logan Webb (W, 15-9)"));
+    assert!(output.ends_with("Venue: Oracle Park.\nSeptember 30, 2022\n"));
+    Ok(())
+}
+
+#[test]
+fn test_box_score_json() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_box_score_with(Some(662021))?;
+
+    let box_score: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!(Value::Null, box_score["linescore"]["innings"][8]["home"]);
+    assert_eq!("Will Smith", box_score["away"]["batters"][2]["name"]);
+    assert_eq!(true, box_score["away"]["batters"][9]["substitute"]);
+    assert_eq!(3, box_score["home"]["pitchers"].as_array().unwrap().len());
+    assert_eq!("Freeman (39, Webb).", box_score["away"]["info"][0]["fields"][0]["value"]);
+    Ok(())
+}
+
+#[test]
+fn test_scoreboard() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_scoreboard_with(Some("2022-09-30"), None)?;
+
+    let output = output(client);
+    assert!(output.contains("\nLAD               | 3          | SF         | 5          | Final       |            | Julio Urías   | Logan Webb"));
+    assert!(output.contains("\nNYY               | 2          | ATL        | 1          | In Progress | Top 5th    |"));
+    assert!(output.contains("\nOakland Athletics |            | LAA        |            | Scheduled   |            | JP Sears      | Shohei Ohtani\n"));
+    Ok(())
+}
+
+#[test]
+fn test_scoreboard_json() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_scoreboard_with(Some("2022-09-30"), None)?;
+
+    let games: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!(662021, games[0]["gamePk"]);
+    assert_eq!("Logan Webb", games[0]["teams"]["home"]["probablePitcher"]["fullName"]);
+    assert!(matches!(create_noninteractive_client()?.get_scoreboard_with(Some("9/30/2022"), None), Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_standings() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_standings_with(None, None, false)?;
+
+    let output = output(client);
+    assert!(output.contains("\nAmerican League East:\nTm                | W          | L          | W-L%       | GB         | WCGB       | Strk"));
+    assert!(output.contains("\nNYY               | 99         | 63         | .611       | -          | -          | W1         | 8-2        | 240        | 51-30      | 48-33"));
+    assert!(output.contains("\nSF                   | 81         | 81         | .500       | 30.0       | 6.0        |"));
+    assert!(output.contains("\nNational League West:"));
+    Ok(())
+}
+
+#[test]
+fn test_wild_card_standings() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_standings_with(None, None, true)?;
+
+    let output = output(client);
+    let american_league = output.split("National League Wild Card").next().unwrap();
+    let position = |team: &str| american_league.find(&format!("\n{} ", team));
+    assert!(position("Toronto Blue Jays") < position("Seattle Mariners"));
+    assert!(position("Seattle Mariners") < position("Tampa Bay Rays"));
+    assert!(position("Tampa Bay Rays") < position("Baltimore Orioles"));
+    assert_eq!(None, position("NYY"));
+    assert_eq!(None, position("Houston Astros"));
+    assert!(output.contains("\nNational League Wild Card:"));
+    Ok(())
+}
+
+#[test]
+fn test_standings_json() -> Result<(), MlbError> {
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_standings_with(None, None, false)?;
+
+    let standings: Value = serde_json::from_str(&output(client)).unwrap();
+    let west = &standings[5];
+    assert_eq!("National League West", west["division"]["name"]);
+    assert_eq!(111, west["teams"][0]["wins"]);
+    assert_eq!(true, west["teams"][0]["divisionLeader"]);
+    assert_eq!(serde_json::json!({"wins": 60, "losses": 21}), west["teams"][0]["home"]);
+    assert!(matches!(create_noninteractive_client()?.get_standings_with(Some("XL"), None, false), Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_roster() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    client.get_roster_with(Some("SF"), Some(RosterType::FortyMan), None)?;
+
+    let output = output(client);
+    assert!(output.contains("SF 40Man Roster:\n#          | Name               | Pos        | B/T        | Status"));
+    assert!(output.contains("\n35         | Brandon Crawford   | SS         | L/R        | Active"));
+    assert!(output.contains("\n26         | Anthony DeSclafani | P          | R/R        | Injured 60-Day"));
+    Ok(())
+}
+
+#[test]
+fn test_roster_player_statline() -> Result<(), MlbError> {
+    let mut client = create_client(&["2022", "active", "62"])?;
+    client.get_roster_player_with(Some("SF"), None, None, None)?;
+
+    let roster = output(client);
+    assert!(roster.contains("SF active Roster:"));
+    assert!(!roster.contains("DeSclafani"));
+    assert!(roster.contains("Enter jersey number or name of a player on the roster: \nPrinting statline for player...\n15 W-9 L, 2.90 ERA"));
+
+    let mut client = create_formatted_client(OutputFormat::Json)?;
+    client.get_roster_player_with(Some("SF"), Some(RosterType::Active), None, Some("bart"))?;
+    let stats: Value = serde_json::from_str(&output(client)).unwrap();
+    assert_eq!("Joey Bart", stats["person"]["fullName"]);
+    Ok(())
+}
+
+#[test]
+fn test_roster_player_not_found() -> Result<(), MlbError> {
+    let mut client = create_noninteractive_client()?;
+    let result = client.get_roster_player_with(Some("SF"), Some(RosterType::Active), None, Some("Ohtani"));
+    assert!(matches!(result, Err(MlbError::InvalidInput(message)) if message == "No player on the roster matching \"Ohtani\""));
+    assert!(matches!("9man".parse::<RosterType>(), Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+// Serves the recorded feed snapshots in order, one per poll, and everything else from the usual fixtures
+fn live_feed_client(format: OutputFormat) -> Result<(TestClient, Rc<Cell<usize>>), MlbError> {
+    let polls = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&polls);
+    let client = MlbClientBuilder::new()
+        .transport(move |url: &Url| {
+            if !url.path().ends_with("/feed/live") {
+                return fixture_transport(url);
+            }
+            counter.set(counter.get() + 1);
+            let path = format!("{}/tests/fixtures/live/feed_662021_{}.json", env!("CARGO_MANIFEST_DIR"), counter.get());
+            Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+        })
+        .season(Some("2022"))
+        .format(format)
+        .build_with_output(Cursor::new(String::new()), Vec::new())?;
+    Ok((client, polls))
+}
+
+#[test]
+fn test_follow_game() -> Result<(), MlbError> {
+    let (mut client, polls) = live_feed_client(OutputFormat::Table)?;
+    client.follow_with(Some("662021"))?;
+
+    assert_eq!(4, polls.get());
+    let output = output(client);
+    assert!(output.starts_with("\nTop 1st\nMookie Betts strikes out swinging.\nFreddie Freeman singles"));
+    assert_eq!(1, output.matches("Freddie Freeman singles").count());
+    assert!(output.contains("Austin Slater scores.\n  Score: LAD 0, SF 1\n"));
+    assert!(output.contains("\nBottom 7th\nPitching Change: Evan Phillips replaces Julio Urías.\nBrandon Crawford grounds out"));
+    assert!(output.ends_with("\nFinal: LAD 3, SF 5\n"));
+    Ok(())
+}
+
+#[test]
+fn test_follow_game_json() -> Result<(), MlbError> {
+    let (mut client, _) = live_feed_client(OutputFormat::Json)?;
+    client.follow_with(Some("662021"))?;
+
+    let events: Vec<Value> = output(client).lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+    assert_eq!(events[0], serde_json::json!({
+        "kind": "inningStart", "inning": 1, "halfInning": "top", "description": "Top 1st", "awayScore": 0, "homeScore": 0
+    }));
+    assert_eq!(1, events.iter().filter(|event| event["kind"] == "pitchingChange").count());
+    assert_eq!("gameOver", events.last().unwrap()["kind"]);
+    Ok(())
+}
+
+#[test]
+fn test_typed_stats_api() -> Result<(), MlbError> {
+    let api = StatsApi::new(MLB_LOOKUP_API_ENDPOINT, Box::new(fixture_transport))?;
+
+    let teams = api.get_teams("2022")?;
+    assert_eq!(Some("SF"), teams[2].abbreviation.as_deref());
+
+    let stats = api.get_team_stats(137, "2022")?;
+    assert_eq!("San Francisco Giants", stats.team.name);
+    assert_eq!(1261, stats.hitting.hits);
+    assert_eq!(81, stats.pitching.wins);
+
+    let boards = api.get_team_stat_leaders(137, "earnedRunAverage", "2022")?;
+    let leader = &boards[0].leaders[1];
+    assert_eq!((2, "Logan Webb", "2.90"), (leader.rank, leader.person.full_name.as_str(), leader.value.as_str()));
+
+    let career = api.get_player_career(663698, &[StatGroup::Hitting], false)?;
+    let hitting = career.hitting.unwrap();
+    assert_eq!(vec!["2020", "2021", "2022"], hitting.seasons.iter().map(|line| line.season.as_str()).collect::<Vec<&str>>());
+    assert_eq!(Some(137), hitting.seasons[0].team.as_ref().map(|team| team.id));
+    assert_eq!(81, hitting.total.unwrap().hits);
+    Ok(())
+}
+
+#[test]
+fn test_custom_base_url() -> Result<(), MlbError> {
+    let transport = |url: &Url| -> Result<Value, MlbError> {
+        assert_eq!(Some("localhost"), url.host_str());
+        assert_eq!(Some(8080), url.port());
+        fixture_transport(url)
+    };
+
+    MlbClientBuilder::new()
+        .base_url("http://localhost:8080/api/v1/")
+        .transport(transport)
+        .build_with_output("2022\nJoey Bart\n".as_bytes(), Vec::new())?
+        .get_player()
+}
+
+#[test]
+fn test_http_status_error() {
+    let transport = |url: &Url| -> Result<Value, MlbError> {
+        Err(MlbError::Status { url: url.to_string(), status: 503 })
+    };
+
+    let result = MlbClientBuilder::new()
+        .transport(transport)
+        .build_with_output("2022\n".as_bytes(), Vec::new());
+    assert!(matches!(result, Err(MlbError::Status { status: 503, .. })));
+}
+
+#[test]
+fn test_record_and_replay() -> Result<(), MlbError> {
+    let dir = temp_dir("record");
+    let input = ["2022", "Logan Webb"].join("\n") + "\n";
+
+    MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .record(&dir)
+        .build_with_output(input.as_bytes(), Vec::new())?
+        .get_player()?;
+    assert!(fs::read_dir(&dir)?.count() >= 3);
+
+    // Replay should never fall through to a transport
+    let offline = |url: &Url| -> Result<Value, MlbError> { panic!("Unexpected request to {}", url) };
+    MlbClientBuilder::new()
+        .transport(offline)
+        .replay(&dir)
+        .build_with_output(input.as_bytes(), Vec::new())?
+        .get_player()?;
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_replay_ignores_host() -> Result<(), MlbError> {
+    let dir = temp_dir("replay-host");
+    let input = ["2022", "Logan Webb"].join("\n") + "\n";
+
+    MlbClientBuilder::new()
+        .transport(fixture_transport)
+        .record(&dir)
+        .build(input.as_bytes())?
+        .get_player()?;
+
+    // Recorded against the default statsapi URL, replayed against a mirror
+    MlbClientBuilder::new()
+        .base_url("http://localhost:8080/api/v1")
+        .replay(&dir)
+        .build(input.as_bytes())?
+        .get_player()?;
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn test_replay_missing_response() -> Result<(), MlbError> {
+    let dir = temp_dir("replay-missing");
+    let result = MlbClientBuilder::new()
+        .replay(&dir)
+        .build_with_output("2022\n".as_bytes(), Vec::new());
+
+    assert!(matches!(result, Err(MlbError::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn test_cached_responses() -> Result<(), MlbError> {
+    let dir = temp_dir("cache");
+    let requests = Rc::new(Cell::new(0));
+    let counter = Rc::clone(&requests);
+    let counting_transport = move |url: &Url| -> Result<Value, MlbError> {
+        counter.set(counter.get() + 1);
+        fixture_transport(url)
+    };
+    let input = ["2022", "Joey Bart", "2022", "Joey Bart"].join("\n") + "\n";
+    let mut reader = Cursor::new(input);
+
+    MlbClientBuilder::new()
+        .transport(counting_transport.clone())
+        .cache_dir(Some(&dir))
+        .build_with_output(&mut reader, Vec::new())?
+        .get_player()?;
+    assert_eq!(3, requests.get());
+
+    // 2022 is a completed season, so everything should come from the cache the second time
+    MlbClientBuilder::new()
+        .transport(counting_transport)
+        .cache_dir(Some(&dir))
+        .build_with_output(&mut reader, Vec::new())?
+        .get_player()?;
+    assert_eq!(3, requests.get());
+
+    assert_eq!(3, mlb::clear_cache(&dir)?);
+    assert_eq!(0, mlb::clear_cache(&dir)?);
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}